@@ -1,10 +1,12 @@
 use std::{
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
     fmt,
     hash::Hash,
     ops::Range,
 };
 
+use crate::riscv::{Abi, Extensions, Xlen};
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub struct Reg(u8);
 
@@ -19,20 +21,123 @@ impl Reg {
         assert!((0..32).contains(&value));
         (value > 0).then_some(Reg(value as u8))
     }
+
+    pub fn number(self) -> u8 {
+        self.0
+    }
+}
+
+/// Where a jump goes, `offset` plus the value of `base` if any
+#[derive(Debug, Clone, Copy)]
+pub struct Target {
+    pub base: Option<Reg>,
+    pub offset: i64,
+}
+
+impl Target {
+    fn addr(addr: i64) -> Self {
+        Target {
+            base: None,
+            offset: addr,
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.base {
+            Some(base) => write!(f, "{} + {base}", self.offset),
+            None => write!(f, "{:#x}", self.offset),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum Operation {
     Nop,
     Unreachable,
-    Tail,
-    Const { dest: Reg, value: i64 },
-    Addi { dest: Reg, base: Reg, offset: i64 },
-    Addiw { dest: Reg, base: Reg, offset: i64 },
-    Add { dest: Reg, lhs: Reg, rhs: Reg },
-    Sub { dest: Reg, lhs: Reg, rhs: Reg },
-    Load { dest: Reg, base: Reg, offset: i64 },
-    Store { val: Reg, base: Reg, offset: i64 },
+    Call(Target),
+    Tail(Target),
+    Const {
+        dest: Reg,
+        value: i64,
+    },
+    Addi {
+        dest: Reg,
+        base: Reg,
+        offset: i64,
+    },
+    Addiw {
+        dest: Reg,
+        base: Reg,
+        offset: i64,
+    },
+    Add {
+        dest: Reg,
+        lhs: Reg,
+        rhs: Reg,
+    },
+    Sub {
+        dest: Reg,
+        lhs: Reg,
+        rhs: Reg,
+    },
+    Mul {
+        dest: Reg,
+        lhs: Reg,
+        rhs: Reg,
+    },
+    Slli {
+        dest: Reg,
+        base: Reg,
+        shamt: i64,
+    },
+    /// `lhs` shifted left by `shamt`, plus `rhs`
+    Shadd {
+        dest: Reg,
+        lhs: Reg,
+        shamt: i64,
+        rhs: Reg,
+    },
+    /// Read of the `vlenb` CSR
+    ReadVlenb {
+        dest: Reg,
+    },
+    Load {
+        dest: Reg,
+        base: Reg,
+        offset: i64,
+    },
+    Store {
+        val: Reg,
+        base: Reg,
+        offset: i64,
+    },
+    /// Store of `size` bytes whose value is not tracked
+    StoreUnknown {
+        base: Reg,
+        offset: i64,
+        size: i64,
+    },
+    /// Store by a vector instruction of `regs` whole vector registers, or of
+    /// an unknown number of bytes from `base` upwards if `None`
+    VectorStore {
+        base: Reg,
+        regs: Option<i64>,
+    },
+    /// `cm.push`, which stores the registers of `rlist` below sp and then
+    /// lowers it by `stack_adj`
+    Push {
+        rlist: i64,
+        stack_adj: i64,
+    },
+    /// `cm.pop` and its variants, which raise sp by `stack_adj` and load the
+    /// registers of `rlist` from below it, and then return if `ret`
+    Pop {
+        rlist: i64,
+        stack_adj: i64,
+        ret: bool,
+    },
 }
 
 impl fmt::Display for Operation {
@@ -42,14 +147,45 @@ impl fmt::Display for Operation {
         match self {
             Nop => write!(f, "nop"),
             Unreachable => write!(f, "unreachable!"),
-            Tail => write!(f, "tail"),
+            Call(target) => write!(f, "call {target}"),
+            Tail(target) => write!(f, "tail {target}"),
             Const { dest, value } => write!(f, "const {dest} <- {value}"),
             Addi { dest, base, offset } => write!(f, "addi {dest} <- {offset} + {base}"),
             Addiw { dest, base, offset } => write!(f, "addiw {dest} <- sx({offset} + {base})"),
             Add { dest, lhs, rhs } => write!(f, "add {dest} <- {lhs} + {rhs}"),
             Sub { dest, lhs, rhs } => write!(f, "sub {dest} <- {lhs} - {rhs}"),
+            Mul { dest, lhs, rhs } => write!(f, "mul {dest} <- {lhs} * {rhs}"),
+            Slli { dest, base, shamt } => write!(f, "slli {dest} <- {base} << {shamt}"),
+            Shadd {
+                dest,
+                lhs,
+                shamt,
+                rhs,
+            } => write!(f, "shadd {dest} <- ({lhs} << {shamt}) + {rhs}"),
+            ReadVlenb { dest } => write!(f, "csrr {dest} <- vlenb"),
             Load { dest, base, offset } => write!(f, "load {dest} <- {offset}({base})"),
             Store { val, base, offset } => write!(f, "store {val} -> {offset}({base})"),
+            StoreUnknown { base, offset, size } => {
+                write!(f, "store ? -> {offset}({base}), {size} bytes")
+            }
+            VectorStore { base, regs } => match regs {
+                Some(regs) => write!(f, "store ? -> 0({base}), {regs} * vlenb bytes"),
+                None => write!(f, "store ? -> 0({base}), ? bytes"),
+            },
+            Push { rlist, stack_adj } => {
+                write!(f, "push rlist {rlist} -> sp, sp <- -{stack_adj} + sp")
+            }
+            Pop {
+                rlist,
+                stack_adj,
+                ret,
+            } => {
+                write!(f, "pop rlist {rlist} <- sp, sp <- {stack_adj} + sp")?;
+                if *ret {
+                    write!(f, ", ret")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -79,10 +215,128 @@ const UNIMP: InsnAnalysis = InsnAnalysis {
     successors: Vec::new(),
 };
 
-pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
+/// Whether a load or store instruction moves a whole register
+fn is_xlen_access(name: &str, xlen: Xlen) -> bool {
+    match xlen {
+        Xlen::Rv32 => matches!(name, "lw" | "sw" | "c.lw" | "c.sw" | "c.lwsp" | "c.swsp"),
+        Xlen::Rv64 => matches!(name, "ld" | "sd" | "c.ld" | "c.sd" | "c.ldsp" | "c.sdsp"),
+    }
+}
+
+/// Size in bytes of the value moved by an integer store narrower than XLEN
+fn narrow_store_size(name: &str) -> i64 {
+    match name.trim_start_matches("c.").trim_end_matches("sp") {
+        "sb" => 1,
+        "sh" => 2,
+        "sw" => 4,
+        _ => unreachable!("{name} is not a narrow store"),
+    }
+}
+
+/// Registers a call may change: ra, t0-t6 and a0-a7, without those from x16
+/// up for the E variants
+fn call_clobbers(abi: Abi) -> Vec<Reg> {
+    [1, 5, 6, 7, 10, 11, 12, 13, 14, 15, 16, 17, 28, 29, 30, 31]
+        .into_iter()
+        .filter(|&n| !abi.is_e() || n < 16)
+        .map(|n| Reg::from(n).unwrap())
+        .collect()
+}
+
+/// A jump to `target` that links to `rd`.
+///
+/// Only calls through ra follow the calling convention. Other link registers
+/// are used to call millicode such as `__riscv_save_*`, which moves sp and
+/// saves registers on the stack in its own way, so such a call may change any
+/// register, sp included.
+fn link(rd: Reg, target: Target, abi: Abi, next: i64) -> InsnAnalysis {
+    if rd.number() == 1 {
+        InsnAnalysis {
+            operation: Operation::Call(target),
+            clobbers: call_clobbers(abi),
+            successors: vec![next],
+        }
+    } else {
+        InsnAnalysis {
+            operation: Operation::Nop,
+            clobbers: (1..32).map(|n| Reg::from(n).unwrap()).collect(),
+            successors: vec![next],
+        }
+    }
+}
+
+/// Registers an `ecall` may change: a0 and a1, which hold the results of both
+/// Linux syscalls and SBI calls
+fn ecall_clobbers() -> Vec<Reg> {
+    vec![Reg::from(10).unwrap(), Reg::from(11).unwrap()]
+}
+
+/// Number of the `vlenb` CSR, which holds the size of a vector register
+const CSR_VLENB: i64 = 0xc22;
+
+/// Number of registers moved by a whole-register vector store, `None` for
+/// other vector stores
+fn whole_vregs(name: &str) -> Option<i64> {
+    match name {
+        "vs1r.v" => Some(1),
+        "vs2r.v" => Some(2),
+        "vs4r.v" => Some(4),
+        "vs8r.v" => Some(8),
+        _ => None,
+    }
+}
+
+/// Registers saved by `cm.push` for its `rlist` field, from the lowest slot
+/// up: ra, then s0 through s11 as far as `rlist` goes. Values below 4 are
+/// reserved, as are those beyond s1 for the E variants.
+fn rlist_regs(rlist: i64, abi: Abi) -> Option<Vec<Reg>> {
+    let count = match rlist {
+        4..=14 => rlist - 3,
+        15 => 13,
+        _ => return None,
+    };
+
+    if abi.is_e() && count > 3 {
+        return None;
+    }
+
+    Some(
+        [1, 8, 9, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27][..count as usize]
+            .iter()
+            .map(|&n| Reg::from(n).unwrap())
+            .collect(),
+    )
+}
+
+/// Bytes that `cm.push` and `cm.pop` move sp by, which is the size of the
+/// saved registers rounded up to 16, plus `spimm`
+fn zcmp_stack_adj(rlist: i64, spimm: i64, abi: Abi) -> Option<i64> {
+    let size = rlist_regs(rlist, abi)?.len() as i64 * abi.xlen().bytes();
+    Some(((size + 15) & !15) + spimm)
+}
+
+/// Size in bytes of the value moved by a floating-point load or store
+fn fp_size(name: &str) -> i64 {
+    match name.trim_start_matches("c.").trim_end_matches("sp") {
+        "flw" | "fsw" => 4,
+        "fld" | "fsd" => 8,
+        "flq" | "fsq" => 16,
+        _ => unreachable!("{name} is not a floating-point load or store"),
+    }
+}
+
+pub fn analyze_insn(
+    pc: i64,
+    range: &Range<i64>,
+    insn: u32,
+    abi: Abi,
+    ext: Extensions,
+) -> InsnAnalysis {
     use Operation::*;
 
-    let enc = if let Some(enc) = crate::riscv::decode(insn) {
+    let xlen = abi.xlen();
+
+    let enc = if let Some(enc) = crate::riscv::decode(insn, xlen, ext) {
         enc
     } else {
         eprintln!("Can't decode {insn:#x}");
@@ -123,6 +377,19 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             }
         }
 
+        "auipc" => InsnAnalysis {
+            operation: if let Some(rd) = Reg::from(fields["rd"]) {
+                Const {
+                    dest: rd,
+                    value: pc.wrapping_add(fields["imm20"]),
+                }
+            } else {
+                Nop
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
         "lui" => InsnAnalysis {
             operation: if let Some(rd) = Reg::from(fields["rd"]) {
                 Const {
@@ -136,7 +403,7 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             successors: vec![next],
         },
 
-        "ld" => {
+        "ld" | "lw" if is_xlen_access(enc.name, xlen) => {
             if let (Some(rd), Some(rs1)) = (Reg::from(fields["rd"]), Reg::from(fields["rs1"])) {
                 InsnAnalysis {
                     operation: Load {
@@ -156,7 +423,7 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             }
         }
 
-        "sd" => {
+        "sd" | "sw" if is_xlen_access(enc.name, xlen) => {
             let operation = if let (Some(rs1), Some(rs2)) =
                 (Reg::from(fields["rs1"]), Reg::from(fields["rs2"]))
             {
@@ -177,41 +444,36 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
         }
 
         "jal" => {
+            let off = fields["jimm20"];
+
             if let Some(rd) = Reg::from(fields["rd"]) {
+                link(rd, Target::addr(pc.wrapping_add(off)), abi, next)
+            } else if range.contains(&pc.wrapping_add(off)) {
                 InsnAnalysis {
                     operation: Nop,
-                    clobbers: vec![rd], // FIXME: ABI clobber
-                    successors: vec![next],
+                    clobbers: Vec::new(),
+                    successors: vec![off],
                 }
             } else {
-                let off = fields["jimm20"];
-
-                if range.contains(&pc.wrapping_add(off)) {
-                    InsnAnalysis {
-                        operation: Nop,
-                        clobbers: Vec::new(),
-                        successors: vec![off],
-                    }
-                } else {
-                    InsnAnalysis {
-                        operation: Tail,
-                        clobbers: Vec::new(),
-                        successors: Vec::new(),
-                    }
+                InsnAnalysis {
+                    operation: Tail(Target::addr(pc.wrapping_add(off))),
+                    clobbers: Vec::new(),
+                    successors: Vec::new(),
                 }
             }
         }
 
         "jalr" => {
+            let target = Target {
+                base: Reg::from(fields["rs1"]),
+                offset: fields["imm12"],
+            };
+
             if let Some(rd) = Reg::from(fields["rd"]) {
-                InsnAnalysis {
-                    operation: Nop,
-                    clobbers: vec![rd], // FIXME: ABI clobber
-                    successors: vec![next],
-                }
+                link(rd, target, abi, next)
             } else {
                 InsnAnalysis {
-                    operation: Tail,
+                    operation: Tail(target),
                     clobbers: Vec::new(),
                     successors: Vec::new(),
                 }
@@ -228,7 +490,7 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
                 }
             } else {
                 InsnAnalysis {
-                    operation: Tail,
+                    operation: Tail(Target::addr(pc.wrapping_add(off))),
                     clobbers: Vec::new(),
                     successors: vec![next],
                 }
@@ -256,25 +518,29 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             successors: vec![next],
         },
 
-        "add" | "sub" => {
+        "add" | "sub" | "mul" => {
             if let (Some(rd), Some(rs1), Some(rs2)) = (
                 Reg::from(fields["rd"]),
                 Reg::from(fields["rs1"]),
                 Reg::from(fields["rs2"]),
             ) {
                 InsnAnalysis {
-                    operation: if enc.name == "add" {
-                        Add {
+                    operation: match enc.name {
+                        "add" => Add {
                             dest: rd,
                             lhs: rs1,
                             rhs: rs2,
-                        }
-                    } else {
-                        Sub {
+                        },
+                        "sub" => Sub {
                             dest: rd,
                             lhs: rs1,
                             rhs: rs2,
-                        }
+                        },
+                        _ => Mul {
+                            dest: rd,
+                            lhs: rs1,
+                            rhs: rs2,
+                        },
                     },
                     clobbers: Vec::new(),
                     successors: vec![next],
@@ -288,21 +554,104 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             }
         }
 
+        "slli" => {
+            let shamt = match xlen {
+                Xlen::Rv32 => fields["shamtw"],
+                Xlen::Rv64 => fields["shamtd"],
+            };
+
+            if let (Some(rd), Some(rs1)) = (Reg::from(fields["rd"]), Reg::from(fields["rs1"])) {
+                InsnAnalysis {
+                    operation: Slli {
+                        dest: rd,
+                        base: rs1,
+                        shamt,
+                    },
+                    clobbers: Vec::new(),
+                    successors: vec![next],
+                }
+            } else {
+                InsnAnalysis {
+                    operation: Nop,
+                    clobbers: Reg::from(fields["rd"]).into_iter().collect(),
+                    successors: vec![next],
+                }
+            }
+        }
+
+        "sh1add" | "sh2add" | "sh3add" => {
+            if let (Some(rd), Some(rs1), Some(rs2)) = (
+                Reg::from(fields["rd"]),
+                Reg::from(fields["rs1"]),
+                Reg::from(fields["rs2"]),
+            ) {
+                InsnAnalysis {
+                    operation: Shadd {
+                        dest: rd,
+                        lhs: rs1,
+                        shamt: match enc.name {
+                            "sh1add" => 1,
+                            "sh2add" => 2,
+                            _ => 3,
+                        },
+                        rhs: rs2,
+                    },
+                    clobbers: Vec::new(),
+                    successors: vec![next],
+                }
+            } else {
+                InsnAnalysis {
+                    operation: Nop,
+                    clobbers: Reg::from(fields["rd"]).into_iter().collect(),
+                    successors: vec![next],
+                }
+            }
+        }
+
+        "csrrs" if fields["csr"] == CSR_VLENB && fields["rs1"] == 0 => InsnAnalysis {
+            operation: if let Some(rd) = Reg::from(fields["rd"]) {
+                ReadVlenb { dest: rd }
+            } else {
+                Nop
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
         // FIXME: Maybe there's a better way...
         #[rustfmt::skip]
-        "auipc" | "lb" | "lh" | "lw" | "lbu" | "lhu" | "lwu" | "slti" | "sltiu" | "xori" | "ori" | "andi" | "sll" | "slt" | "sltu" | "xor" | "srl" | "sra" | "or" | "and" | "slli" | "srli" | "srai"
+        "lb" | "lh" | "lw" | "lbu" | "lhu" | "lwu" | "slti" | "sltiu" | "xori" | "ori" | "andi" | "sll" | "slt" | "sltu" | "xor" | "srl" | "sra" | "or" | "and" | "srli" | "srai"
         | "slliw" | "srliw" | "sraiw" | "addw" | "subw" | "sllw" | "srlw" | "sraw"
-        | "mul" | "mulh" | "mulhsu" | "mulhu" | "div" | "divu" | "rem" | "remu" | "mulw" | "divw" | "divuw" | "remw" | "remuw"
+        | "mulh" | "mulhsu" | "mulhu" | "div" | "divu" | "rem" | "remu" | "mulw" | "divw" | "divuw" | "remw" | "remuw"
         | "amoswap.w" | "amoadd.w" | "amoxor.w" | "amoand.w" | "amoor.w" | "amomin.w" | "amomax.w" | "amominu.w" | "amomaxu.w"
         | "amoswap.d" | "amoadd.d" | "amoxor.d" | "amoand.d" | "amoor.d" | "amomin.d" | "amomax.d" | "amominu.d" | "amomaxu.d"
         | "lr.w" | "sc.w" | "lr.d" | "sc.d"
+        | "add.uw" | "sh1add.uw" | "sh2add.uw" | "sh3add.uw" | "slli.uw"
+        | "andn" | "orn" | "xnor" | "clz" | "clzw" | "ctz" | "ctzw" | "cpop" | "cpopw" | "max" | "maxu" | "min" | "minu"
+        | "sext.b" | "sext.h" | "zext.h" | "rol" | "rolw" | "ror" | "rori" | "roriw" | "rorw" | "orc.b" | "rev8"
+        | "clmul" | "clmulh" | "clmulr"
+        | "bclr" | "bclri" | "bext" | "bexti" | "binv" | "binvi" | "bset" | "bseti"
         | "csrrw" | "csrrs" | "csrrc" | "csrrwi" | "csrrsi" | "csrrci" => InsnAnalysis {
             operation: Nop,
             clobbers: Reg::from(fields["rd"]).into_iter().collect(),
             successors: vec![next],
         },
 
-        "sb" | "sh" | "sw" | "fence" | "c.sw" | "c.swsp" => InsnAnalysis {
+        "sb" | "sh" | "sw" => InsnAnalysis {
+            operation: if let Some(rs1) = Reg::from(fields["rs1"]) {
+                StoreUnknown {
+                    base: rs1,
+                    offset: fields["imm12hilo"],
+                    size: narrow_store_size(enc.name),
+                }
+            } else {
+                Nop
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "fence" => InsnAnalysis {
             operation: Nop,
             clobbers: Vec::new(),
             successors: vec![next],
@@ -314,14 +663,17 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             successors: vec![],
         },
 
-        "ecall" | "fence.i" | "wfi" | "sfence.vma" => {
-            // FIXME: Handle ecall
-            InsnAnalysis {
-                operation: Nop,
-                clobbers: Vec::new(),
-                successors: vec![next],
-            }
-        }
+        "ecall" => InsnAnalysis {
+            operation: Nop,
+            clobbers: ecall_clobbers(),
+            successors: vec![next],
+        },
+
+        "fence.i" | "wfi" | "sfence.vma" => InsnAnalysis {
+            operation: Nop,
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
 
         "c.addi" => InsnAnalysis {
             operation: Addi {
@@ -410,7 +762,7 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
                 }
             } else {
                 InsnAnalysis {
-                    operation: Tail,
+                    operation: Tail(Target::addr(pc.wrapping_add(off))),
                     clobbers: Vec::new(),
                     successors: vec![next],
                 }
@@ -428,7 +780,7 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
                 }
             } else {
                 InsnAnalysis {
-                    operation: Tail,
+                    operation: Tail(Target::addr(pc.wrapping_add(off))),
                     clobbers: Vec::new(),
                     successors: vec![],
                 }
@@ -436,43 +788,69 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
         }
 
         "c.jr" => InsnAnalysis {
-            operation: Tail,
+            operation: Tail(Target {
+                base: Reg::from(fields["rs1_n0"]),
+                offset: 0,
+            }),
             clobbers: Vec::new(),
             successors: vec![],
         },
 
-        "c.jalr" => InsnAnalysis {
-            operation: Nop,
-            clobbers: vec![Reg::from(1).unwrap()], // FIXME: ABI clobber
-            successors: vec![next],
-        },
+        "c.jal" => link(
+            Reg::from(1).unwrap(),
+            Target::addr(pc.wrapping_add(fields["c_imm12"])),
+            abi,
+            next,
+        ),
+
+        "c.jalr" => link(
+            Reg::from(1).unwrap(),
+            Target {
+                base: Reg::from(fields["c_rs1_n0"]),
+                offset: 0,
+            },
+            abi,
+            next,
+        ),
 
-        "c.ld" => InsnAnalysis {
+        "c.ld" | "c.lw" if is_xlen_access(enc.name, xlen) => InsnAnalysis {
             operation: Load {
                 dest: Reg::from(fields["rd_p"]).unwrap(),
                 base: Reg::from(fields["rs1_p"]).unwrap(),
-                offset: fields["c_uimm8hilo"],
+                offset: if enc.name == "c.ld" {
+                    fields["c_uimm8hilo"]
+                } else {
+                    fields["c_uimm7hilo"]
+                },
             },
             clobbers: Vec::new(),
             successors: vec![next],
         },
 
-        "c.sd" => InsnAnalysis {
+        "c.sd" | "c.sw" if is_xlen_access(enc.name, xlen) => InsnAnalysis {
             operation: Store {
                 val: Reg::from(fields["rs2_p"]).unwrap(),
                 base: Reg::from(fields["rs1_p"]).unwrap(),
-                offset: fields["c_uimm8hilo"],
+                offset: if enc.name == "c.sd" {
+                    fields["c_uimm8hilo"]
+                } else {
+                    fields["c_uimm7hilo"]
+                },
             },
             clobbers: Vec::new(),
             successors: vec![next],
         },
 
-        "c.ldsp" => InsnAnalysis {
+        "c.ldsp" | "c.lwsp" if is_xlen_access(enc.name, xlen) => InsnAnalysis {
             operation: if let Some(rd) = Reg::from(fields["rd_n0"]) {
                 Load {
                     dest: rd,
                     base: Reg::from(2).unwrap(),
-                    offset: fields["c_uimm9sphilo"],
+                    offset: if enc.name == "c.ldsp" {
+                        fields["c_uimm9sphilo"]
+                    } else {
+                        fields["c_uimm8sphilo"]
+                    },
                 }
             } else {
                 Nop
@@ -481,12 +859,16 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             successors: vec![next],
         },
 
-        "c.sdsp" => InsnAnalysis {
+        "c.sdsp" | "c.swsp" if is_xlen_access(enc.name, xlen) => InsnAnalysis {
             operation: if let Some(rs2) = Reg::from(fields["c_rs2"]) {
                 Store {
                     val: rs2,
                     base: Reg::from(2).unwrap(),
-                    offset: fields["c_uimm9sp_s"],
+                    offset: if enc.name == "c.sdsp" {
+                        fields["c_uimm9sp_s"]
+                    } else {
+                        fields["c_uimm8sp_s"]
+                    },
                 }
             } else {
                 Nop
@@ -551,8 +933,32 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
         },
 
         "c.slli" => InsnAnalysis {
-            operation: Nop,
-            clobbers: Reg::from(fields["rd_rs1_n0"]).into_iter().collect(),
+            operation: Slli {
+                dest: Reg::from(fields["rd_rs1_n0"]).unwrap(),
+                base: Reg::from(fields["rd_rs1_n0"]).unwrap(),
+                shamt: fields["c_nzuimm6hilo"],
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "c.sw" => InsnAnalysis {
+            operation: StoreUnknown {
+                base: Reg::from(fields["rs1_p"]).unwrap(),
+                offset: fields["c_uimm7hilo"],
+                size: narrow_store_size(enc.name),
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "c.swsp" => InsnAnalysis {
+            operation: StoreUnknown {
+                base: Reg::from(2).unwrap(),
+                offset: fields["c_uimm8sp_s"],
+                size: narrow_store_size(enc.name),
+            },
+            clobbers: Vec::new(),
             successors: vec![next],
         },
 
@@ -568,6 +974,175 @@ pub fn analyze_insn(pc: i64, range: &Range<i64>, insn: u32) -> InsnAnalysis {
             successors: vec![next],
         },
 
+        "c.sb" | "c.sh" => InsnAnalysis {
+            operation: StoreUnknown {
+                base: Reg::from(fields["rs1_p"]).unwrap(),
+                offset: if enc.name == "c.sh" {
+                    fields["c_uimm1"]
+                } else {
+                    fields["c_uimm2"]
+                },
+                size: narrow_store_size(enc.name),
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "c.lbu" | "c.lhu" | "c.lh" => InsnAnalysis {
+            operation: Nop,
+            clobbers: Reg::from(fields["rd_p"]).into_iter().collect(),
+            successors: vec![next],
+        },
+
+        "c.zext.b" | "c.sext.b" | "c.zext.h" | "c.sext.h" | "c.zext.w" | "c.not" => InsnAnalysis {
+            operation: Nop,
+            clobbers: Reg::from(fields["rd_rs1_p"]).into_iter().collect(),
+            successors: vec![next],
+        },
+
+        "c.mul" => InsnAnalysis {
+            operation: Mul {
+                dest: Reg::from(fields["rd_rs1_p"]).unwrap(),
+                lhs: Reg::from(fields["rd_rs1_p"]).unwrap(),
+                rhs: Reg::from(fields["rs2_p"]).unwrap(),
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "cm.push" | "cm.pop" | "cm.popret" | "cm.popretz" => {
+            let rlist = fields["c_rlist"];
+            let Some(stack_adj) = zcmp_stack_adj(rlist, fields["c_spimm"], abi) else {
+                return UNIMP;
+            };
+
+            match enc.name {
+                "cm.push" => InsnAnalysis {
+                    operation: Push { rlist, stack_adj },
+                    clobbers: Vec::new(),
+                    successors: vec![next],
+                },
+                "cm.pop" => InsnAnalysis {
+                    operation: Pop {
+                        rlist,
+                        stack_adj,
+                        ret: false,
+                    },
+                    clobbers: Vec::new(),
+                    successors: vec![next],
+                },
+                _ => InsnAnalysis {
+                    operation: Pop {
+                        rlist,
+                        stack_adj,
+                        ret: true,
+                    },
+                    // cm.popretz also sets a0 to zero
+                    clobbers: if enc.name == "cm.popretz" {
+                        vec![Reg::from(10).unwrap()]
+                    } else {
+                        Vec::new()
+                    },
+                    successors: Vec::new(),
+                },
+            }
+        }
+
+        "cm.mvsa01" => InsnAnalysis {
+            operation: Nop,
+            clobbers: vec![
+                Reg::from(fields["c_sreg1"]).unwrap(),
+                Reg::from(fields["c_sreg2"]).unwrap(),
+            ],
+            successors: vec![next],
+        },
+
+        "cm.mva01s" => InsnAnalysis {
+            operation: Nop,
+            clobbers: vec![Reg::from(10).unwrap(), Reg::from(11).unwrap()],
+            successors: vec![next],
+        },
+
+        "fsw" | "fsd" | "fsq" => InsnAnalysis {
+            operation: if let Some(rs1) = Reg::from(fields["rs1"]) {
+                StoreUnknown {
+                    base: rs1,
+                    offset: fields["imm12hilo"],
+                    size: fp_size(enc.name),
+                }
+            } else {
+                Nop
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "c.fsw" | "c.fsd" => InsnAnalysis {
+            operation: StoreUnknown {
+                base: Reg::from(fields["rs1_p"]).unwrap(),
+                offset: if enc.name == "c.fsd" {
+                    fields["c_uimm8hilo"]
+                } else {
+                    fields["c_uimm7hilo"]
+                },
+                size: fp_size(enc.name),
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "c.fswsp" | "c.fsdsp" => InsnAnalysis {
+            operation: StoreUnknown {
+                base: Reg::from(2).unwrap(),
+                offset: if enc.name == "c.fsdsp" {
+                    fields["c_uimm9sp_s"]
+                } else {
+                    fields["c_uimm8sp_s"]
+                },
+                size: fp_size(enc.name),
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        "vsetvli" | "vsetivli" | "vsetvl" => InsnAnalysis {
+            operation: Nop,
+            clobbers: Reg::from(fields["rd"]).into_iter().collect(),
+            successors: vec![next],
+        },
+
+        // Vector stores, the only instructions with a vs3 field
+        _ if enc.fields.contains(&"vs3") => InsnAnalysis {
+            operation: if let Some(rs1) = Reg::from(fields["rs1"]) {
+                VectorStore {
+                    base: rs1,
+                    regs: whole_vregs(enc.name),
+                }
+            } else {
+                Nop
+            },
+            clobbers: Vec::new(),
+            successors: vec![next],
+        },
+
+        // Other floating-point and vector instructions only write integer
+        // registers through rd, such as fmv.x.w, feq.s and vmv.x.s
+        _ if enc
+            .fields
+            .iter()
+            .any(|&f| crate::riscv::is_freg_field(f) || crate::riscv::is_vreg_field(f)) =>
+        {
+            InsnAnalysis {
+                operation: Nop,
+                clobbers: if enc.fields.contains(&"rd") {
+                    Reg::from(fields["rd"]).into_iter().collect()
+                } else {
+                    Vec::new()
+                },
+                successors: vec![next],
+            }
+        }
+
         "c.unimp" | "c.ebreak" => UNIMP,
 
         _ => {
@@ -583,48 +1158,117 @@ pub enum KnownValue {
     OrigSp(i64),
     OrigFp,
     OrigRa,
+    /// Original value of a register other than sp, fp and ra
+    OrigReg(Reg),
+    /// `scale` times the value of `vlenb`, plus `offset`
+    Vlenb {
+        scale: i64,
+        offset: i64,
+    },
+    /// Like `Vlenb`, but also plus the original sp
+    OrigSpVlenb {
+        scale: i64,
+        offset: i64,
+    },
 }
 
 impl KnownValue {
-    fn addi(self, offset: i64) -> Option<Self> {
+    /// Splits a sum of the original sp, a multiple of `vlenb` and a constant
+    /// into whether it has the original sp, the multiple and the constant.
+    fn linear(self) -> Option<(bool, i64, i64)> {
         use KnownValue::*;
 
         match self {
-            Abs(val) => Some(Abs(val.wrapping_add(offset))),
-            OrigSp(val) => Some(OrigSp(val.wrapping_add(offset))),
-            OrigFp => None,
-            OrigRa => None,
+            Abs(val) => Some((false, 0, val)),
+            OrigSp(val) => Some((true, 0, val)),
+            Vlenb { scale, offset } => Some((false, scale, offset)),
+            OrigSpVlenb { scale, offset } => Some((true, scale, offset)),
+            OrigFp | OrigRa | OrigReg(_) => None,
         }
     }
 
+    /// Inverse of `linear`
+    fn from_linear(sp: bool, scale: i64, offset: i64) -> Self {
+        use KnownValue::*;
+
+        match (sp, scale) {
+            (false, 0) => Abs(offset),
+            (true, 0) => OrigSp(offset),
+            (false, _) => Vlenb { scale, offset },
+            (true, _) => OrigSpVlenb { scale, offset },
+        }
+    }
+
+    fn addi(self, offset: i64) -> Option<Self> {
+        let (sp, scale, val) = self.linear()?;
+        Some(Self::from_linear(sp, scale, val.wrapping_add(offset)))
+    }
+
     fn addiw(self, offset: i64) -> Option<Self> {
         use KnownValue::*;
 
         match self {
             Abs(val) => Some(Abs(val.wrapping_add(offset) as i32 as i64)),
-            OrigSp(_) => None,
-            OrigFp => None,
-            OrigRa => None,
+            _ => None,
         }
     }
 
     fn add(&self, rv: KnownValue) -> Option<KnownValue> {
-        use KnownValue::*;
+        let (lsp, lscale, loff) = self.linear()?;
+        let (rsp, rscale, roff) = rv.linear()?;
 
-        match (*self, rv) {
-            (Abs(labs), rv) => rv.addi(labs),
-            (lv, Abs(rabs)) => lv.addi(rabs),
-            _ => None,
+        if lsp && rsp {
+            return None;
         }
+
+        Some(Self::from_linear(
+            lsp || rsp,
+            lscale.wrapping_add(rscale),
+            loff.wrapping_add(roff),
+        ))
     }
 
     fn sub(&self, rv: KnownValue) -> Option<KnownValue> {
+        let (lsp, lscale, loff) = self.linear()?;
+        let (rsp, rscale, roff) = rv.linear()?;
+
+        if rsp && !lsp {
+            return None;
+        }
+
+        Some(Self::from_linear(
+            lsp && !rsp,
+            lscale.wrapping_sub(rscale),
+            loff.wrapping_sub(roff),
+        ))
+    }
+
+    fn mul(&self, rv: KnownValue) -> Option<KnownValue> {
         use KnownValue::*;
 
-        if let Abs(rabs) = rv {
-            self.addi(rabs.wrapping_neg())
-        } else {
-            None
+        let (val, factor) = match (*self, rv) {
+            (val, Abs(factor)) | (Abs(factor), val) => (val, factor),
+            _ => return None,
+        };
+
+        match val.linear()? {
+            (false, scale, offset) => Some(Self::from_linear(
+                false,
+                scale.wrapping_mul(factor),
+                offset.wrapping_mul(factor),
+            )),
+            (true, _, _) => None,
+        }
+    }
+
+    fn slli(self, shamt: i64) -> Option<Self> {
+        match self.linear()? {
+            (false, scale, offset) => Some(Self::from_linear(
+                false,
+                scale.wrapping_shl(shamt as u32),
+                offset.wrapping_shl(shamt as u32),
+            )),
+            (true, _, _) => None,
         }
     }
 }
@@ -638,12 +1282,16 @@ impl fmt::Display for KnownValue {
             OrigSp(val) => write!(f, "_sp + {val}"),
             OrigFp => write!(f, "_fp"),
             OrigRa => write!(f, "_ra"),
+            OrigReg(reg) => write!(f, "_{reg}"),
+            Vlenb { scale, offset } => write!(f, "{scale} * vlenb + {offset}"),
+            OrigSpVlenb { scale, offset } => write!(f, "_sp + {scale} * vlenb + {offset}"),
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct AbstractState {
+    xlen: Xlen,
     regs: BTreeMap<Reg, KnownValue>,
     stack: BTreeMap<i64, KnownValue>,
 }
@@ -652,16 +1300,17 @@ pub struct AbstractState {
 pub enum OrigSpReg {
     Sp,
     Fp,
+    Other(Reg),
 }
 
 impl fmt::Display for OrigSpReg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use OrigSpReg::*;
-        let name = match self {
-            Sp => "sp",
-            Fp => "fp",
-        };
-        write!(f, "{name}")
+        match self {
+            Sp => write!(f, "sp"),
+            Fp => write!(f, "fp"),
+            Other(reg) => write!(f, "{reg}"),
+        }
     }
 }
 
@@ -669,14 +1318,35 @@ impl fmt::Display for OrigSpReg {
 pub struct UnwindStep {
     pub sp_reg: OrigSpReg,
     pub sp_offset: i64,
+    /// Multiple of `vlenb` also added to `sp_reg` for the caller's sp
+    pub sp_vlenb: i64,
     pub fp_offset: Option<i64>,
     pub ra_offset: Option<i64>,
+    /// Where to find the caller's value of each callee-saved register other
+    /// than fp: still in the register if `None`, else at this offset from the
+    /// original sp. Registers not in the map cannot be recovered.
+    pub saved: BTreeMap<Reg, Option<i64>>,
+    /// `sp_reg + sp_offset` is the address of a trap frame, see `trap_step`
+    pub trap: bool,
 }
 
 impl fmt::Display for UnwindStep {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vlenb = match self.sp_vlenb {
+            0 => String::new(),
+            scale => format!(" + {scale} * vlenb"),
+        };
+
+        if self.trap {
+            return write!(
+                f,
+                "[trap frame = {} + {}{vlenb}]",
+                self.sp_reg, self.sp_offset
+            );
+        }
+
         write!(f, "[")?;
-        write!(f, "_sp = {} + {}", self.sp_reg, self.sp_offset)?;
+        write!(f, "_sp = {} + {}{vlenb}", self.sp_reg, self.sp_offset)?;
         if let Some(off) = self.fp_offset {
             write!(f, ", _fp = {off}(_sp)")?;
         } else {
@@ -687,10 +1357,64 @@ impl fmt::Display for UnwindStep {
         } else {
             write!(f, ", _ra = ra")?;
         }
+        for reg in saved_regs() {
+            match self.saved.get(&reg) {
+                Some(Some(off)) => write!(f, ", _{reg} = {off}(_sp)")?,
+                Some(None) => {}
+                None => write!(f, ", _{reg} = ?")?,
+            }
+        }
         write!(f, "]")
     }
 }
 
+/// Whether some real `vlenb >= 1` satisfies `a * vlenb < b` for every `(a, b)`
+/// in `constraints`
+fn satisfiable(constraints: &[(i64, i64)]) -> bool {
+    // Exclusive bounds on vlenb, as fractions with positive denominators
+    let mut lower: Option<(i128, i128)> = None;
+    let mut upper: Option<(i128, i128)> = None;
+    let less = |(p1, q1): (i128, i128), (p2, q2): (i128, i128)| p1 * q2 < p2 * q1;
+
+    for &(a, b) in constraints {
+        let (a, b) = (a as i128, b as i128);
+
+        if a > 0 {
+            if upper.is_none_or(|upper| less((b, a), upper)) {
+                upper = Some((b, a));
+            }
+        } else if a < 0 {
+            if lower.is_none_or(|lower| less(lower, (-b, -a))) {
+                lower = Some((-b, -a));
+            }
+        } else if b <= 0 {
+            return false;
+        }
+    }
+
+    match upper {
+        Some(upper) => less((1, 1), upper) && lower.is_none_or(|lower| less(lower, upper)),
+        None => true,
+    }
+}
+
+/// Value of `reg` on entry to a function
+fn orig_value(reg: Reg) -> KnownValue {
+    match reg.number() {
+        1 => KnownValue::OrigRa,
+        2 => KnownValue::OrigSp(0),
+        8 => KnownValue::OrigFp,
+        _ => KnownValue::OrigReg(reg),
+    }
+}
+
+/// Callee-saved registers other than fp, `s1` through `s11`
+pub fn saved_regs() -> impl Iterator<Item = Reg> {
+    rvind_unwinder::SAVED_REG_NUMBERS
+        .into_iter()
+        .map(|n| Reg::from(n as i64).unwrap())
+}
+
 fn merge_map<K: Ord, V: Eq>(current: &mut BTreeMap<K, V>, other: &BTreeMap<K, V>) -> bool {
     let mut changed = false;
 
@@ -717,7 +1441,8 @@ impl AbstractState {
         match op {
             Nop => {}
             Unreachable => {}
-            Tail => {}
+            Call(_) => {}
+            Tail(_) => {}
             Const { dest, value } => {
                 self.regs.insert(dest, Abs(value));
             }
@@ -765,6 +1490,49 @@ impl AbstractState {
                     self.regs.remove(&dest);
                 }
             }
+            Mul { dest, lhs, rhs } => {
+                if let (Some(lv), Some(rv)) = (self.regs.get(&lhs), self.regs.get(&rhs)) {
+                    if let Some(new_val) = lv.mul(*rv) {
+                        self.regs.insert(dest, new_val);
+                    } else {
+                        self.regs.remove(&dest);
+                    }
+                } else {
+                    self.regs.remove(&dest);
+                }
+            }
+            Slli { dest, base, shamt } => {
+                if let Some(new_val) = self.regs.get(&base).and_then(|v| v.slli(shamt)) {
+                    self.regs.insert(dest, new_val);
+                } else {
+                    self.regs.remove(&dest);
+                }
+            }
+            Shadd {
+                dest,
+                lhs,
+                shamt,
+                rhs,
+            } => {
+                if let (Some(lv), Some(rv)) = (self.regs.get(&lhs), self.regs.get(&rhs)) {
+                    if let Some(new_val) = lv.slli(shamt).and_then(|lv| lv.add(*rv)) {
+                        self.regs.insert(dest, new_val);
+                    } else {
+                        self.regs.remove(&dest);
+                    }
+                } else {
+                    self.regs.remove(&dest);
+                }
+            }
+            ReadVlenb { dest } => {
+                self.regs.insert(
+                    dest,
+                    Vlenb {
+                        scale: 1,
+                        offset: 0,
+                    },
+                );
+            }
             Load { dest, base, offset } => {
                 if let Some(OrigSp(sp_off)) = self.regs.get(&base).and_then(|v| v.addi(offset)) {
                     if let Some(val) = self.stack.get(&sp_off) {
@@ -778,17 +1546,109 @@ impl AbstractState {
             }
 
             Store { val, base, offset } => {
-                if let Some(OrigSp(sp_off)) = self.regs.get(&base).and_then(|v| v.addi(offset)) {
-                    if let Some(val) = self.regs.get(&val) {
-                        self.stack.insert(sp_off, *val);
-                    } else {
-                        self.stack.remove(&sp_off);
+                match self.regs.get(&base).and_then(|v| v.addi(offset)) {
+                    Some(OrigSp(sp_off)) => {
+                        if let Some(val) = self.regs.get(&val) {
+                            self.stack.insert(sp_off, *val);
+                        } else {
+                            self.stack.remove(&sp_off);
+                        }
                     }
+                    Some(addr) => self.forget_stored(addr, Some((self.xlen.bytes(), 0))),
+                    None => {}
+                }
+            }
+
+            StoreUnknown { base, offset, size } => {
+                if let Some(addr) = self.regs.get(&base).and_then(|v| v.addi(offset)) {
+                    self.forget_stored(addr, Some((size, 0)));
                 }
             }
+
+            VectorStore { base, regs } => {
+                if let Some(&addr) = self.regs.get(&base) {
+                    self.forget_stored(addr, regs.map(|regs| (0, regs)));
+                }
+            }
+
+            Push { rlist, stack_adj } => {
+                let sp = Reg::from(2).unwrap();
+                let xlen = self.xlen.bytes();
+                let regs = rlist_regs(rlist, Abi::new(self.xlen, false)).unwrap();
+                let count = regs.len() as i64;
+
+                // The last register goes right below sp, and ra the lowest
+                for (i, val) in regs.into_iter().enumerate() {
+                    self.execute_operation(Store {
+                        val,
+                        base: sp,
+                        offset: (i as i64 - count) * xlen,
+                    });
+                }
+
+                self.execute_operation(Addi {
+                    dest: sp,
+                    base: sp,
+                    offset: -stack_adj,
+                });
+            }
+
+            Pop {
+                rlist, stack_adj, ..
+            } => {
+                let sp = Reg::from(2).unwrap();
+                let xlen = self.xlen.bytes();
+                let regs = rlist_regs(rlist, Abi::new(self.xlen, false)).unwrap();
+                let count = regs.len() as i64;
+
+                for (i, dest) in regs.into_iter().enumerate() {
+                    self.execute_operation(Load {
+                        dest,
+                        base: sp,
+                        offset: stack_adj + (i as i64 - count) * xlen,
+                    });
+                }
+
+                self.execute_operation(Addi {
+                    dest: sp,
+                    base: sp,
+                    offset: stack_adj,
+                });
+            }
         }
     }
 
+    /// Forgets every stack slot a store to `addr` may share a byte with.
+    ///
+    /// The store is `size.0` plus `size.1` times `vlenb` bytes long, or has no
+    /// known end if `size` is `None`. Slots are kept only if they cannot
+    /// overlap for any `vlenb`.
+    fn forget_stored(&mut self, addr: KnownValue, size: Option<(i64, i64)>) {
+        let (scale, offset) = match addr.linear() {
+            Some((true, scale, offset)) => (scale, offset),
+            _ => return,
+        };
+
+        let xlen = self.xlen.bytes();
+
+        self.stack.retain(|&off, _| {
+            // The store starts below the end of the slot...
+            let starts_below = (scale, off.wrapping_add(xlen).wrapping_sub(offset));
+
+            match size {
+                // ... and ends above its start
+                Some((size, size_scale)) => {
+                    let ends_above = (
+                        scale.wrapping_add(size_scale).wrapping_neg(),
+                        offset.wrapping_add(size).wrapping_sub(off),
+                    );
+                    !satisfiable(&[starts_below, ends_above])
+                }
+                None => !satisfiable(&[starts_below]),
+            }
+        });
+    }
+
     fn execute(&mut self, analysis: &InsnAnalysis) {
         for c in &analysis.clobbers {
             self.regs.remove(c);
@@ -796,6 +1656,37 @@ impl AbstractState {
         self.execute_operation(analysis.operation);
     }
 
+    /// Address that a jump to `target` goes to, if known
+    fn target(&self, target: Target) -> Option<i64> {
+        let base = match target.base {
+            Some(reg) => match self.regs.get(&reg)? {
+                KnownValue::Abs(val) => *val,
+                _ => return None,
+            },
+            None => 0,
+        };
+
+        let addr = base.wrapping_add(target.offset) & !1;
+
+        Some(match self.xlen {
+            Xlen::Rv32 => addr as u32 as i64,
+            Xlen::Rv64 => addr,
+        })
+    }
+
+    /// Whether a jump to `target` returns to the caller
+    fn is_return(&self, target: Target) -> bool {
+        let base = target.base.and_then(|reg| self.regs.get(&reg));
+        target.offset == 0 && base == Some(&KnownValue::OrigRa)
+    }
+
+    /// Registers no longer known to hold their value on entry
+    fn changed_regs(&self) -> impl Iterator<Item = Reg> + '_ {
+        (1..32)
+            .map(|n| Reg::from(n).unwrap())
+            .filter(|&reg| self.regs.get(&reg) != Some(&orig_value(reg)))
+    }
+
     fn merge(&mut self, other: &Self) -> bool {
         merge_map(&mut self.regs, &other.regs) || merge_map(&mut self.stack, &other.stack)
     }
@@ -804,6 +1695,8 @@ impl AbstractState {
         use KnownValue::*;
         use Operation::*;
 
+        let xlen = self.xlen.bytes();
+
         match self.regs.get(&Reg::from(8).unwrap()) {
             Some(OrigFp) => {
                 println!("fp = original fp");
@@ -815,13 +1708,13 @@ impl AbstractState {
             }
             Some(OrigSp(off)) => {
                 println!("new fp");
-                if let Some(OrigRa) = self.stack.get(&off.wrapping_sub(8)) {
+                if let Some(OrigRa) = self.stack.get(&off.wrapping_sub(xlen)) {
                     println!("saved ra okay");
                 } else {
                     println!("saved ra invalid!");
                 }
 
-                if let Some(OrigFp) = self.stack.get(&off.wrapping_sub(16)) {
+                if let Some(OrigFp) = self.stack.get(&off.wrapping_sub(2 * xlen)) {
                     println!("saved fp okay");
                 } else {
                     println!("saved fp invalid!");
@@ -832,7 +1725,7 @@ impl AbstractState {
             }
         }
 
-        if let Tail = &insn.operation {
+        if let Tail(_) = &insn.operation {
             if Some(OrigRa) != self.regs.get(&Reg::from(1).unwrap()).copied() {
                 println!("bad ra != _ra at tail");
             }
@@ -847,18 +1740,45 @@ impl AbstractState {
         }
     }
 
+    /// Finds a register holding the original sp, preferring sp, then fp, then
+    /// s1 through s11. Other registers are not recovered below the first
+    /// frame, so they are never used.
+    ///
+    /// Registers at a fixed offset from the original sp are preferred over
+    /// those also off by a multiple of `vlenb`, which the unwinder may not
+    /// know. Returns the register, the offset and the multiple.
+    fn orig_sp(&self) -> Option<(OrigSpReg, i64, i64)> {
+        use KnownValue::*;
+
+        let sp = Reg::from(2).unwrap();
+        let fp = Reg::from(8).unwrap();
+
+        let candidates = || {
+            [(sp, OrigSpReg::Sp), (fp, OrigSpReg::Fp)]
+                .into_iter()
+                .chain(saved_regs().map(|reg| (reg, OrigSpReg::Other(reg))))
+        };
+
+        candidates()
+            .find_map(|(reg, sp_reg)| match self.regs.get(&reg)? {
+                OrigSp(offset) => Some((sp_reg, offset.wrapping_neg(), 0)),
+                _ => None,
+            })
+            .or_else(|| {
+                candidates().find_map(|(reg, sp_reg)| match self.regs.get(&reg)? {
+                    OrigSpVlenb { scale, offset } => {
+                        Some((sp_reg, offset.wrapping_neg(), scale.wrapping_neg()))
+                    }
+                    _ => None,
+                })
+            })
+    }
+
     pub fn unwind_step(&self) -> Option<UnwindStep> {
         use KnownValue::*;
 
         // Find original sp
-        let (sp_reg, sp_offset) =
-            if let Some(OrigSp(offset)) = self.regs.get(&Reg::from(2).unwrap()) {
-                (OrigSpReg::Sp, offset.wrapping_neg())
-            } else if let Some(OrigSp(offset)) = self.regs.get(&Reg::from(8).unwrap()) {
-                (OrigSpReg::Fp, offset.wrapping_neg())
-            } else {
-                return None;
-            };
+        let (sp_reg, sp_offset, sp_vlenb) = self.orig_sp()?;
 
         let fp_offset = if let Some(OrigFp) = self.regs.get(&Reg::from(8).unwrap()) {
             None
@@ -872,11 +1792,72 @@ impl AbstractState {
             Some(*self.stack.iter().find(|(_off, &val)| val == OrigRa)?.0)
         };
 
+        let saved = saved_regs()
+            .filter_map(|reg| {
+                if let Some(&OrigReg(r)) = self.regs.get(&reg) {
+                    if r == reg {
+                        return Some((reg, None));
+                    }
+                }
+
+                let (off, _) = self.stack.iter().find(|(_off, &val)| val == OrigReg(reg))?;
+                Some((reg, Some(*off)))
+            })
+            .collect();
+
         Some(UnwindStep {
             sp_reg,
             sp_offset,
+            sp_vlenb,
             fp_offset,
             ra_offset,
+            saved,
+            trap: false,
+        })
+    }
+
+    /// Unwind step for trap entry code, which saves the trapped pc and `x1`
+    /// through `x31` into a trap frame with one register-sized slot each.
+    ///
+    /// The trap frame is located by where the original ra was saved. Every
+    /// slot of it with a known value must hold the original value of its
+    /// register, the original fp must be among them, and only one such frame
+    /// may be found.
+    pub fn trap_step(&self) -> Option<UnwindStep> {
+        use KnownValue::*;
+
+        let (sp_reg, sp_offset, sp_vlenb) = self.orig_sp()?;
+        let xlen = self.xlen.bytes();
+
+        let matches = |base: i64| {
+            (1..32).all(|num| {
+                let reg = Reg::from(num).unwrap();
+                let slot = self.stack.get(&base.wrapping_add(num * xlen));
+                slot.is_none_or(|&val| val == orig_value(reg))
+            }) && self.stack.get(&base.wrapping_add(8 * xlen)) == Some(&OrigFp)
+        };
+
+        let mut bases = self
+            .stack
+            .iter()
+            .filter(|(_off, &val)| val == OrigRa)
+            .map(|(off, _val)| off.wrapping_sub(xlen))
+            .filter(|&base| matches(base));
+
+        let base = bases.next()?;
+
+        if bases.next().is_some() {
+            return None;
+        }
+
+        Some(UnwindStep {
+            sp_reg,
+            sp_offset: sp_offset.wrapping_add(base),
+            sp_vlenb,
+            fp_offset: None,
+            ra_offset: None,
+            saved: BTreeMap::new(),
+            trap: true,
         })
     }
 }
@@ -885,6 +1866,11 @@ impl fmt::Display for AbstractState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut fl = f.debug_list();
         for (reg, val) in &self.regs {
+            // Untouched callee-saved registers would only add noise
+            if *val == KnownValue::OrigReg(*reg) {
+                continue;
+            }
+
             fl.entry(&format_args!("{reg} = {val}"));
         }
 
@@ -895,21 +1881,44 @@ impl fmt::Display for AbstractState {
     }
 }
 
-pub fn analyze(addr: i64, bytes: &[u8]) -> HashMap<i64, AbstractState> {
+#[derive(Debug)]
+pub struct FunctionAnalysis {
+    pub states: HashMap<i64, AbstractState>,
+    /// Clobbers of the calls that used the callee's summary, by address
+    pub calls: HashMap<i64, Vec<Reg>>,
+    /// Registers the function may change before it returns
+    pub clobbers: Vec<Reg>,
+}
+
+/// Analyzes the function at `addr`.
+///
+/// `summary` gives the registers the function at an address may change, and
+/// is used for calls and tail jumps to known addresses. Those it returns
+/// `None` for clobber what the ABI allows.
+pub fn analyze(
+    addr: i64,
+    bytes: &[u8],
+    abi: Abi,
+    ext: Extensions,
+    summary: &mut dyn FnMut(i64) -> Option<Vec<Reg>>,
+) -> FunctionAnalysis {
+    let xlen = abi.xlen();
     let range = addr..addr + (bytes.len() as i64);
 
     let mut res: HashMap<i64, AbstractState> = HashMap::new();
+    let mut calls: HashMap<i64, Vec<Reg>> = HashMap::new();
+    // Jumps out of the function, with the state after them
+    let mut exits: HashMap<i64, (Target, AbstractState)> = HashMap::new();
     let mut queue: VecDeque<i64> = VecDeque::new();
     queue.push_back(addr);
     res.insert(
         addr,
         AbstractState {
-            regs: [
-                (Reg::from(1).unwrap(), KnownValue::OrigRa),
-                (Reg::from(2).unwrap(), KnownValue::OrigSp(0)),
-                (Reg::from(8).unwrap(), KnownValue::OrigFp),
-            ]
-            .into(),
+            xlen,
+            regs: (1..32)
+                .map(|n| Reg::from(n).unwrap())
+                .map(|reg| (reg, orig_value(reg)))
+                .collect(),
             stack: [].into(),
         },
     );
@@ -926,10 +1935,39 @@ pub fn analyze(addr: i64, bytes: &[u8]) -> HashMap<i64, AbstractState> {
                 u16::from_le_bytes(bytes[off..][..2].try_into().unwrap()) as u32
             };
 
-            let analysis = analyze_insn(pc, &range, insn);
+            let mut analysis = analyze_insn(pc, &range, insn, abi, ext);
+
+            if let Operation::Call(target) = analysis.operation {
+                if let Some(mut clobbers) = state.target(target).and_then(&mut *summary) {
+                    // Linking always changes ra
+                    let ra = Reg::from(1).unwrap();
+                    if !clobbers.contains(&ra) {
+                        clobbers.insert(0, ra);
+                    }
+
+                    analysis.clobbers = clobbers;
+                    calls.insert(pc, analysis.clobbers.clone());
+                } else {
+                    calls.remove(&pc);
+                }
+            }
 
             state.execute(&analysis);
 
+            match analysis.operation {
+                Operation::Tail(target) => {
+                    exits.insert(pc, (target, state.clone()));
+                }
+                Operation::Pop { ret: true, .. } => {
+                    let target = Target {
+                        base: Reg::from(1),
+                        offset: 0,
+                    };
+                    exits.insert(pc, (target, state.clone()));
+                }
+                _ => {}
+            }
+
             for succ in &analysis.successors {
                 if let Some(s) = res.get_mut(&(pc + succ)) {
                     if s.merge(&state) {
@@ -943,5 +1981,194 @@ pub fn analyze(addr: i64, bytes: &[u8]) -> HashMap<i64, AbstractState> {
         }
     }
 
-    res
+    let mut clobbers: BTreeSet<Reg> = BTreeSet::new();
+
+    for (target, state) in exits.into_values() {
+        clobbers.extend(state.changed_regs());
+
+        if !state.is_return(target) {
+            match state.target(target).and_then(&mut *summary) {
+                Some(callee) => clobbers.extend(callee),
+                None => clobbers.extend(call_clobbers(abi)),
+            }
+        }
+    }
+
+    FunctionAnalysis {
+        states: res,
+        calls,
+        clobbers: clobbers.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// State after running `insns`, all 32 bits wide, from the start of a
+    /// function on RV64
+    fn state_after(insns: &[u32]) -> AbstractState {
+        let bytes: Vec<u8> = insns.iter().flat_map(|i| i.to_le_bytes()).collect();
+        let analysis = analyze(0, &bytes, Abi::Lp64, Extensions::default(), &mut |_| None);
+        analysis.states[&(bytes.len() as i64)].clone()
+    }
+
+    /// Analysis of a function of 16-bit instructions on RV64 with Zcmp
+    fn analyze_zcmp(insns: &[u16]) -> FunctionAnalysis {
+        let bytes: Vec<u8> = insns.iter().flat_map(|i| i.to_le_bytes()).collect();
+        let ext = Extensions { zcmp: true };
+        analyze(0, &bytes, Abi::Lp64, ext, &mut |_| None)
+    }
+
+    const TRAP_ENTRY: [u32; 5] = [
+        0xf0010113, // addi sp, sp, -256
+        0x00113423, // sd ra, 8(sp)
+        0x04813023, // sd s0, 64(sp)
+        0xff010113, // addi sp, sp, -16
+        0x00113423, // sd ra, 8(sp)
+    ];
+
+    #[test]
+    fn trap_frame_ignores_other_ra_slots() {
+        let step = state_after(&TRAP_ENTRY).trap_step().unwrap();
+
+        assert!(step.trap);
+        assert_eq!(step.sp_reg, OrigSpReg::Sp);
+        assert_eq!(step.sp_offset, 16);
+    }
+
+    #[test]
+    fn trap_frame_rejects_mismatched_slot() {
+        let mut insns = TRAP_ENTRY.to_vec();
+        insns.extend([
+            0x00500493, // li s1, 5
+            0x04913c23, // sd s1, 88(sp)
+        ]);
+
+        assert_eq!(state_after(&insns).trap_step(), None);
+    }
+
+    #[test]
+    fn trap_frame_accepts_saved_regs() {
+        let mut insns = TRAP_ENTRY.to_vec();
+        insns.push(0x04913c23); // sd s1, 88(sp)
+
+        assert!(state_after(&insns).trap_step().is_some());
+    }
+
+    #[test]
+    fn narrow_stores_overwrite_slots() {
+        let state = state_after(&[
+            0xff010113, // addi sp, sp, -16
+            0x00113423, // sd ra, 8(sp)
+            0x00813023, // sd s0, 0(sp)
+            0x00012423, // sw zero, 8(sp)
+            0x000103a3, // sb zero, 7(sp)
+        ]);
+
+        assert_eq!(state.stack.get(&-8), None);
+        assert_eq!(state.stack.get(&-16), None);
+    }
+
+    #[test]
+    fn orig_sp_only_in_saved_regs() {
+        let s1 = Reg::from(9).unwrap();
+
+        let state = state_after(&[
+            0x00010493, // mv s1, sp
+            0x40a10133, // sub sp, sp, a0
+        ]);
+        assert_eq!(state.orig_sp(), Some((OrigSpReg::Other(s1), 0, 0)));
+
+        let state = state_after(&[
+            0x00010293, // mv t0, sp
+            0x40a10133, // sub sp, sp, a0
+        ]);
+        assert_eq!(state.orig_sp(), None);
+    }
+
+    #[test]
+    fn millicode_call_clobbers_sp() {
+        // jal t0, 0x100, as used to call __riscv_save_*
+        let state = state_after(&[0x100002ef]);
+
+        assert_eq!(state.regs.get(&Reg::from(2).unwrap()), None);
+        assert_eq!(state.regs.get(&Reg::from(8).unwrap()), None);
+        assert_eq!(state.unwind_step(), None);
+    }
+
+    #[test]
+    fn vlenb_sized_frame() {
+        const PROLOGUE: [u32; 4] = [
+            0xff010113, // addi sp, sp, -16
+            0x00113423, // sd ra, 8(sp)
+            0xc22022f3, // csrr t0, vlenb
+            0x40510133, // sub sp, sp, t0
+        ];
+
+        // A spill of a whole vector register below the ra slot
+        let state = state_after(&[&PROLOGUE[..], &[0x028100a7]].concat()); // vs1r.v v1, (sp)
+        let step = state.unwind_step().unwrap();
+
+        assert_eq!(step.sp_reg, OrigSpReg::Sp);
+        assert_eq!((step.sp_offset, step.sp_vlenb), (16, 1));
+        assert_eq!(state.stack.get(&-8), Some(&KnownValue::OrigRa));
+
+        // One that overwrites it, whatever vlenb is
+        let state = state_after(
+            &[
+                &PROLOGUE[..],
+                &[
+                    0x01010313, // addi t1, sp, 16
+                    0x02830127, // vs1r.v v2, (t1)
+                ],
+            ]
+            .concat(),
+        );
+
+        assert_eq!(state.stack.get(&-8), None);
+    }
+
+    #[test]
+    fn shadd_of_sp() {
+        let reg = |n| Reg::from(n).unwrap();
+        let state = state_after(&[
+            0xfe010113, // addi sp, sp, -32
+            0x00400293, // li t0, 4
+            0x2022a333, // sh1add t1, t0, sp
+            0x2022e3b3, // sh3add t2, t0, sp
+            0x20512e33, // sh1add t3, sp, t0
+        ]);
+
+        assert_eq!(state.regs.get(&reg(6)), Some(&KnownValue::OrigSp(-24)));
+        assert_eq!(state.regs.get(&reg(7)), Some(&KnownValue::OrigSp(0)));
+        // Twice the original sp is not tracked
+        assert_eq!(state.regs.get(&reg(28)), None);
+    }
+
+    #[test]
+    fn zcmp_push_popret_slots() {
+        let analysis = analyze_zcmp(&[
+            0xb866, // cm.push {ra, s0-s1}, -48
+            0x0001, // c.nop
+            0xbe66, // cm.popret {ra, s0-s1}, 48
+        ]);
+        let state = &analysis.states[&2];
+
+        // ra in the lowest slot and the last register right below the
+        // original sp, whatever the extra stack adjustment
+        assert_eq!(
+            state.stack,
+            [
+                (-24, KnownValue::OrigRa),
+                (-16, KnownValue::OrigFp),
+                (-8, KnownValue::OrigReg(Reg::from(9).unwrap())),
+            ]
+            .into()
+        );
+        assert_eq!(state.unwind_step().unwrap().sp_offset, 48);
+
+        // cm.popret restores everything from the same slots
+        assert!(analysis.clobbers.is_empty());
+    }
 }
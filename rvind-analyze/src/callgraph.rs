@@ -0,0 +1,177 @@
+//! Bottom-up pass over the call graph, so that each call to a known function
+//! clobbers what that function may change instead of what the ABI allows.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{self, FunctionAnalysis, Reg},
+    riscv::{Abi, Extensions},
+};
+
+/// A function whose analysis has not finished, because it calls itself
+/// through a cycle of functions.
+struct InProgress {
+    addr: i64,
+    /// Registers assumed to change for the calls back into the function,
+    /// which grow until its analysis agrees with them
+    assumed: Vec<Reg>,
+    /// Whether the latest analysis of the function used `assumed`
+    used: bool,
+}
+
+struct CallGraph<'a> {
+    abi: Abi,
+    ext: Extensions,
+    functions: HashMap<i64, &'a [u8]>,
+    results: HashMap<i64, FunctionAnalysis>,
+    /// Innermost last
+    in_progress: Vec<InProgress>,
+    /// Outermost entry of `in_progress` whose assumption the analysis going on
+    /// used, if any
+    depends_on: Option<usize>,
+}
+
+impl CallGraph<'_> {
+    /// Analyzes the function at `addr` after the functions it calls, and
+    /// returns the registers it may change.
+    ///
+    /// Functions in a cycle are analyzed again until their results no longer
+    /// change, and only kept once the outermost of them is done. Returns
+    /// `None` if there is no function at `addr`, in which case the ABI
+    /// applies.
+    fn summary(&mut self, addr: i64) -> Option<Vec<Reg>> {
+        if let Some(result) = self.results.get(&addr) {
+            return Some(result.clobbers.clone());
+        }
+
+        if let Some(index) = self.in_progress.iter().position(|f| f.addr == addr) {
+            self.depends_on = Some(self.depends_on.map_or(index, |d| d.min(index)));
+            self.in_progress[index].used = true;
+            return Some(self.in_progress[index].assumed.clone());
+        }
+
+        let bytes = *self.functions.get(&addr)?;
+        let (abi, ext) = (self.abi, self.ext);
+        let index = self.in_progress.len();
+        let outer_depends_on = self.depends_on;
+
+        self.in_progress.push(InProgress {
+            addr,
+            assumed: Vec::new(),
+            used: false,
+        });
+
+        let (result, depends_on) = loop {
+            self.depends_on = None;
+            self.in_progress[index].used = false;
+            let result =
+                analysis::analyze(addr, bytes, abi, ext, &mut |callee| self.summary(callee));
+            let f = &mut self.in_progress[index];
+
+            if !f.used || result.clobbers.iter().all(|r| f.assumed.contains(r)) {
+                break (result, self.depends_on.filter(|&d| d < index));
+            }
+
+            f.assumed.extend(&result.clobbers);
+            f.assumed.sort_unstable();
+            f.assumed.dedup();
+        };
+
+        self.in_progress.pop();
+        let clobbers = result.clobbers.clone();
+
+        match depends_on {
+            // Only valid for the assumptions made for the outer function, so
+            // it is analyzed again once they have settled
+            Some(d) => self.depends_on = Some(outer_depends_on.map_or(d, |o| o.min(d))),
+            None => {
+                self.depends_on = outer_depends_on;
+                self.results.insert(addr, result);
+            }
+        }
+
+        Some(clobbers)
+    }
+}
+
+/// Analyzes each function given by its address and code, callees first.
+pub fn analyze_all<'a>(
+    functions: impl IntoIterator<Item = (i64, &'a [u8])>,
+    abi: Abi,
+    ext: Extensions,
+) -> HashMap<i64, FunctionAnalysis> {
+    let mut graph = CallGraph {
+        abi,
+        ext,
+        functions: functions.into_iter().collect(),
+        results: HashMap::new(),
+        in_progress: Vec::new(),
+        depends_on: None,
+    };
+
+    let mut addrs: Vec<i64> = graph.functions.keys().copied().collect();
+    addrs.sort_unstable();
+
+    for addr in addrs {
+        graph.summary(addr);
+    }
+
+    graph.results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze_functions(functions: &[(i64, &[u32])]) -> HashMap<i64, FunctionAnalysis> {
+        let code: Vec<(i64, Vec<u8>)> = functions
+            .iter()
+            .map(|(addr, insns)| (*addr, insns.iter().flat_map(|i| i.to_le_bytes()).collect()))
+            .collect();
+
+        analyze_all(
+            code.iter().map(|(addr, bytes)| (*addr, &bytes[..])),
+            Abi::Lp64,
+            Extensions::default(),
+        )
+    }
+
+    #[test]
+    fn nonstandard_callees() {
+        let t0 = Reg::from(5).unwrap();
+        let s1 = Reg::from(9).unwrap();
+
+        let results = analyze_functions(&[
+            // jal 0x2000; ret
+            (0x1000, &[0x000010ef, 0x00008067]),
+            // jal 0x3000; ret
+            (0x1100, &[0x701010ef, 0x00008067]),
+            // li a0, 1; ret
+            (0x2000, &[0x00100513, 0x00008067]),
+            // li s1, 5; ret
+            (0x3000, &[0x00500493, 0x00008067]),
+        ]);
+
+        // t0 survives a call to a function that leaves it alone
+        assert!(!results[&0x1000].calls[&0x1000].contains(&t0));
+        // s1 does not survive a call to one that changes it
+        assert!(results[&0x1100].calls[&0x1100].contains(&s1));
+    }
+
+    #[test]
+    fn recursive_callees() {
+        let s2 = Reg::from(18).unwrap();
+
+        let results = analyze_functions(&[
+            // jal 0x5000; li s2, 5; ret
+            (0x4000, &[0x000010ef, 0x00500913, 0x00008067]),
+            // jal 0x4000; ret
+            (0x5000, &[0x800ff0ef, 0x00008067]),
+        ]);
+
+        // Each changes s2 through the other
+        assert!(results[&0x4000].clobbers.contains(&s2));
+        assert!(results[&0x5000].clobbers.contains(&s2));
+        assert!(results[&0x5000].calls[&0x5000].contains(&s2));
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::{bail, Result};
+use goblin::elf;
+use rvind_unwinder::MemoryReader;
+use std::ops::Range;
+
+use crate::riscv::Xlen;
+
+const NT_PRSTATUS: u32 = 1;
+const NT_AUXV: u32 = 6;
+const AT_ENTRY: u64 = 9;
+
+/// Registers of one thread, as saved in its `NT_PRSTATUS` note.
+#[derive(Debug)]
+pub struct Thread {
+    pub pid: u32,
+    pub signal: u16,
+    /// `regs[0]` is pc, and `regs[i]` is `x{i}` otherwise
+    pub regs: [u64; 32],
+}
+
+#[derive(Debug)]
+pub struct Segment {
+    pub addr: u64,
+    /// Size in memory, which may be more than the size in the file
+    pub mem_size: u64,
+    pub data: Range<usize>,
+}
+
+#[derive(Debug)]
+pub struct Core {
+    pub xlen: Xlen,
+    pub threads: Vec<Thread>,
+    pub segments: Vec<Segment>,
+    /// Run-time address of the entry point, if the core has an `NT_AUXV` note
+    pub entry: Option<u64>,
+}
+
+fn read_word(bytes: &[u8], off: usize, xlen: Xlen) -> Option<u64> {
+    let bytes = bytes.get(off..off + xlen.bytes() as usize)?;
+
+    Some(match xlen {
+        Xlen::Rv32 => u32::from_le_bytes(bytes.try_into().unwrap()) as u64,
+        Xlen::Rv64 => u64::from_le_bytes(bytes.try_into().unwrap()),
+    })
+}
+
+impl Thread {
+    fn from_prstatus(desc: &[u8], xlen: Xlen) -> Option<Self> {
+        // Offsets into struct elf_prstatus
+        let (pid_off, reg_off) = match xlen {
+            Xlen::Rv32 => (24, 72),
+            Xlen::Rv64 => (32, 112),
+        };
+        let word = xlen.bytes() as usize;
+
+        let mut regs = [0; 32];
+
+        for (i, reg) in regs.iter_mut().enumerate() {
+            *reg = read_word(desc, reg_off + i * word, xlen)?;
+        }
+
+        Some(Self {
+            pid: u32::from_le_bytes(desc.get(pid_off..pid_off + 4)?.try_into().unwrap()),
+            signal: u16::from_le_bytes(desc.get(12..14)?.try_into().unwrap()),
+            regs,
+        })
+    }
+}
+
+impl Core {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let elf = elf::Elf::parse(bytes)?;
+
+        if elf.header.e_type != elf::header::ET_CORE {
+            bail!("Not a core file");
+        }
+
+        if elf.header.e_machine != elf::header::EM_RISCV || !elf.little_endian {
+            bail!("Not a little endian RISC-V core file");
+        }
+
+        let xlen = if elf.is_64 { Xlen::Rv64 } else { Xlen::Rv32 };
+
+        let mut threads = Vec::new();
+        let mut entry = None;
+
+        for note in elf.iter_note_headers(bytes).into_iter().flatten() {
+            let note = note?;
+
+            match note.n_type {
+                NT_PRSTATUS => {
+                    let Some(thread) = Thread::from_prstatus(note.desc, xlen) else {
+                        bail!("Truncated NT_PRSTATUS note");
+                    };
+                    threads.push(thread);
+                }
+                NT_AUXV => {
+                    let word = xlen.bytes() as usize;
+
+                    for off in (0..note.desc.len()).step_by(2 * word) {
+                        let key = read_word(note.desc, off, xlen);
+                        let value = read_word(note.desc, off + word, xlen);
+
+                        if let (Some(AT_ENTRY), Some(value)) = (key, value) {
+                            entry = Some(value);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let segments = elf
+            .program_headers
+            .iter()
+            .filter(|ph| ph.p_type == elf::program_header::PT_LOAD)
+            .map(|ph| Segment {
+                addr: ph.p_vaddr,
+                mem_size: ph.p_memsz,
+                data: ph.file_range(),
+            })
+            .filter(|seg| seg.data.end <= bytes.len())
+            .collect();
+
+        Ok(Self {
+            xlen,
+            threads,
+            segments,
+            entry,
+        })
+    }
+
+    pub fn segment(&self, addr: u64) -> Option<&Segment> {
+        self.segments
+            .iter()
+            .find(|seg| (seg.addr..seg.addr + seg.mem_size).contains(&addr))
+    }
+}
+
+/// Serves reads from the `PT_LOAD` segments of a core file.
+pub struct CoreMemory<'a> {
+    pub core: &'a Core,
+    pub bytes: &'a [u8],
+}
+
+impl MemoryReader for CoreMemory<'_> {
+    fn read_usize(&self, addr: usize) -> Option<usize> {
+        let addr = addr as u64;
+        let word = self.core.xlen.bytes() as u64;
+
+        if !addr.is_multiple_of(word) {
+            return None;
+        }
+
+        let seg = self.core.segment(addr)?;
+        let off = addr - seg.addr;
+
+        // Parts of the segment that are not in the file were not dumped
+        if off + word > seg.data.len() as u64 {
+            return None;
+        }
+
+        let data = &self.bytes[seg.data.clone()];
+        read_word(data, off as usize, self.core.xlen).map(|w| w as usize)
+    }
+
+    fn word_size(&self) -> usize {
+        self.core.xlen.bytes() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An `NT_PRSTATUS` descriptor laid out field by field like the kernel's
+    /// `struct elf_prstatus`, with pc at 0x1000 and `x{i}` set to `i`.
+    fn prstatus(xlen: Xlen, pid: u32, signal: u16) -> Vec<u8> {
+        let mut desc = Vec::new();
+        let word = |desc: &mut Vec<u8>, value: u64| {
+            desc.extend(&value.to_le_bytes()[..xlen.bytes() as usize]);
+        };
+
+        desc.extend([0; 12]); // pr_info
+        desc.extend(signal.to_le_bytes()); // pr_cursig
+        desc.extend([0; 2]);
+        word(&mut desc, 0); // pr_sigpend
+        word(&mut desc, 0); // pr_sighold
+        desc.extend(pid.to_le_bytes()); // pr_pid
+        desc.extend([0; 12]); // pr_ppid, pr_pgrp, pr_sid
+
+        // pr_utime, pr_stime, pr_cutime, pr_cstime
+        for _ in 0..8 {
+            word(&mut desc, 0);
+        }
+
+        // pr_reg
+        word(&mut desc, 0x1000);
+        for i in 1..32 {
+            word(&mut desc, i);
+        }
+
+        desc.extend([0; 4]); // pr_fpvalid
+        desc
+    }
+
+    #[test]
+    fn prstatus_offsets() {
+        for xlen in [Xlen::Rv32, Xlen::Rv64] {
+            let desc = prstatus(xlen, 42, 11);
+            let thread = Thread::from_prstatus(&desc, xlen).unwrap();
+            let mut regs: [u64; 32] = core::array::from_fn(|i| i as u64);
+            regs[0] = 0x1000;
+
+            assert_eq!((thread.pid, thread.signal), (42, 11), "{xlen:?}");
+            assert_eq!(thread.regs, regs, "{xlen:?}");
+
+            // Missing part of x31
+            assert!(Thread::from_prstatus(&desc[..desc.len() - 5], xlen).is_none());
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use anyhow::{anyhow, bail, Result};
+use rvind_unwinder::Lsda;
+use std::collections::HashMap;
+
+use crate::{
+    elf::{Executable, Section},
+    riscv::Xlen,
+};
+
+const DW_EH_PE_OMIT: u8 = 0xff;
+const DW_EH_PE_PCREL: u8 = 0x10;
+const DW_EH_PE_FUNCREL: u8 = 0x40;
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Address of `data[0]`
+    addr: u64,
+    xlen: Xlen,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos + len).ok_or(anyhow!(
+            "Unexpected end of data at {:#x}",
+            self.addr + self.pos as u64
+        ))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn uint(&mut self, len: usize) -> Result<u64> {
+        let mut buf = [0; 8];
+        buf[..len].copy_from_slice(self.bytes(len)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn sint(&mut self, len: usize) -> Result<i64> {
+        let shift = 64 - 8 * len as u32;
+        Ok(((self.uint(len)? << shift) as i64) >> shift)
+    }
+
+    fn uleb128(&mut self) -> Result<u64> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as u64).checked_shl(shift).unwrap_or(0);
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn sleb128(&mut self) -> Result<i64> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as i64).checked_shl(shift).unwrap_or(0);
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= -1 << shift;
+                }
+                return Ok(value);
+            }
+        }
+    }
+
+    fn cstr(&mut self) -> Result<&'a [u8]> {
+        let len = self.data[self.pos..]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(anyhow!("Unterminated string"))?;
+        let s = self.bytes(len)?;
+        self.pos += 1;
+        Ok(s)
+    }
+
+    /// Reads a value in the format given by the low bits of `enc`.
+    fn value(&mut self, enc: u8) -> Result<u64> {
+        Ok(match enc & 0x0f {
+            0x00 => self.uint(self.xlen.bytes() as usize)?,
+            0x01 => self.uleb128()?,
+            0x02 => self.uint(2)?,
+            0x03 => self.uint(4)?,
+            0x04 => self.uint(8)?,
+            0x09 => self.sleb128()? as u64,
+            0x0a => self.sint(2)? as u64,
+            0x0b => self.sint(4)? as u64,
+            0x0c => self.sint(8)? as u64,
+            _ => bail!("Unsupported pointer encoding {enc:#x}"),
+        })
+    }
+
+    /// Reads a pointer encoded as `enc`. Indirect pointers are not followed.
+    fn pointer(&mut self, enc: u8, func_start: u64) -> Result<u64> {
+        let addr = self.addr + self.pos as u64;
+        let value = self.value(enc)?;
+
+        Ok(match enc & 0x70 {
+            0x00 => value,
+            DW_EH_PE_PCREL => addr.wrapping_add(value),
+            DW_EH_PE_FUNCREL => func_start.wrapping_add(value),
+            _ => bail!("Unsupported pointer encoding {enc:#x}"),
+        })
+    }
+}
+
+/// A function with an LSDA, from the FDE describing it.
+struct Function {
+    start: u64,
+    len: u64,
+    lsda: u64,
+}
+
+struct Cie {
+    fde_enc: u8,
+    lsda_enc: u8,
+    has_aug_data: bool,
+}
+
+fn parse_cie(r: &mut Reader) -> Result<Cie> {
+    let version = r.u8()?;
+    let aug = r.cstr()?;
+
+    if aug.starts_with(b"eh") {
+        r.value(0)?;
+    }
+
+    r.uleb128()?; // Code alignment
+    r.sleb128()?; // Data alignment
+
+    if version == 1 {
+        r.u8()?;
+    } else {
+        r.uleb128()?;
+    }
+
+    let mut cie = Cie {
+        fde_enc: 0,
+        lsda_enc: DW_EH_PE_OMIT,
+        has_aug_data: aug.first() == Some(&b'z'),
+    };
+
+    if !cie.has_aug_data {
+        return Ok(cie);
+    }
+
+    let len = r.uleb128()? as usize;
+    let end = r.pos + len;
+
+    for c in &aug[1..] {
+        match c {
+            b'P' => {
+                let enc = r.u8()?;
+                r.pointer(enc, 0)?;
+            }
+            b'L' => cie.lsda_enc = r.u8()?,
+            b'R' => cie.fde_enc = r.u8()?,
+            b'S' | b'B' => {}
+            // The rest of the augmentation data is skipped below
+            _ => break,
+        }
+    }
+
+    r.pos = end;
+    Ok(cie)
+}
+
+fn parse_eh_frame(data: &[u8], addr: u64, xlen: Xlen) -> Result<Vec<Function>> {
+    let mut r = Reader {
+        data,
+        pos: 0,
+        addr,
+        xlen,
+    };
+    let mut cies: HashMap<usize, Cie> = HashMap::new();
+    let mut functions: Vec<Function> = Vec::new();
+
+    while r.pos < data.len() {
+        let mut len = r.uint(4)?;
+
+        if len == 0 {
+            break;
+        }
+
+        if len == 0xffff_ffff {
+            len = r.uint(8)?;
+        }
+
+        let start = r.pos;
+        let end = start + len as usize;
+        let cie_pointer = r.uint(4)? as usize;
+
+        if cie_pointer == 0 {
+            cies.insert(start - 4, parse_cie(&mut r)?);
+        } else {
+            let cie = start
+                .checked_sub(cie_pointer)
+                .and_then(|off| cies.get(&off))
+                .ok_or(anyhow!("FDE at {:#x} has no CIE", addr + start as u64))?;
+            let func_start = r.pointer(cie.fde_enc, 0)?;
+            let func_len = r.value(cie.fde_enc)?;
+
+            if cie.has_aug_data {
+                r.uleb128()?;
+
+                if cie.lsda_enc != DW_EH_PE_OMIT {
+                    let lsda = r.pointer(cie.lsda_enc, func_start)?;
+
+                    if lsda != 0 {
+                        functions.push(Function {
+                            start: func_start,
+                            len: func_len,
+                            lsda,
+                        });
+                    }
+                }
+            }
+        }
+
+        r.pos = end;
+    }
+
+    Ok(functions)
+}
+
+/// Functions with an LSDA, from `.eh_frame` if there is one.
+fn functions(exe: &Executable, buf: &[u8]) -> Result<Vec<Function>> {
+    match exe.sections.iter().find(|s| s.name == ".eh_frame") {
+        Some(eh_frame) => parse_eh_frame(&buf[eh_frame.data.clone()], eh_frame.addr, exe.xlen),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Locates the LSDA of each function in `.text` that has one, for the
+/// personality routine.
+pub fn lsdas(exe: &Executable, buf: &[u8], text: &Section) -> Result<Vec<Lsda>> {
+    let text_end = text.addr + text.data.len() as u64;
+    let mut lsdas = Vec::new();
+
+    for f in functions(exe, buf)? {
+        if f.start < text.addr || f.start.saturating_add(f.len) > text_end {
+            eprintln!("Function {:#x} with an LSDA outside .text", f.start);
+            continue;
+        }
+
+        let lsda: i32 = (f.lsda.wrapping_sub(text.addr) as i64)
+            .try_into()
+            .map_err(|_| anyhow!("LSDA {:#x} too far from .text", f.lsda))?;
+
+        lsdas.push(Lsda {
+            code_offset: (f.start - text.addr).try_into()?,
+            code_len: f.len.try_into()?,
+            lsda: lsda as u32,
+            reserved: 0,
+        });
+    }
+
+    lsdas.sort_unstable_by_key(|l| l.code_offset);
+
+    Ok(lsdas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eh_frame_lsda() {
+        let mut data = Vec::new();
+        // CIE with augmentation "zLR", and PC-relative sdata4 pointers
+        data.extend(16u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend([1, b'z', b'L', b'R', 0, 1, 0x78, 1, 2, 0x1b, 0x1b, 0]);
+        // FDE for 0x1000..0x1040, with its LSDA at 0x3000
+        data.extend(20u32.to_le_bytes());
+        data.extend(24u32.to_le_bytes());
+        data.extend((0x1000 - 0x201ci32).to_le_bytes());
+        data.extend(0x40u32.to_le_bytes());
+        data.push(4);
+        data.extend((0x3000 - 0x2025i32).to_le_bytes());
+        data.extend([0; 3]);
+        data.extend(0u32.to_le_bytes());
+
+        let functions: Vec<_> = parse_eh_frame(&data, 0x2000, Xlen::Rv64)
+            .unwrap()
+            .iter()
+            .map(|f| (f.start, f.len, f.lsda))
+            .collect();
+
+        assert_eq!(functions, [(0x1000, 0x40, 0x3000)]);
+    }
+}
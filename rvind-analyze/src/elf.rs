@@ -2,12 +2,22 @@ use anyhow::{bail, Result};
 use goblin::{container::Ctx, elf, strtab::Strtab};
 use std::{collections::HashMap, ops::Range};
 
+use crate::riscv::{Abi, Extensions, Xlen};
+
+/// `e_flags` bit marking code for RV32E/RV64E
+const EF_RISCV_RVE: u32 = 0x8;
+
+/// Attribute holding the ISA string, e.g. `rv32i2p1_m2p0_c2p0_zcmp1p0`
+const TAG_RISCV_ARCH: u64 = 5;
+
 #[derive(Debug, Clone)]
 pub struct Symbol {
     pub name: String,
     pub section: usize,
     pub addr: u64,
     pub size: u64,
+    /// Whether other objects can link against the symbol
+    pub global: bool,
 }
 
 impl Symbol {
@@ -17,11 +27,13 @@ impl Symbol {
             section: sym.st_shndx,
             addr: sym.st_value,
             size: sym.st_size,
+            global: sym.st_bind() == elf::sym::STB_GLOBAL,
         }
     }
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub struct Relocation {
     pub symbol: Symbol,
     pub ty: u32,
@@ -60,6 +72,13 @@ impl Section {
 
 #[derive(Debug)]
 pub struct Executable {
+    pub xlen: Xlen,
+    /// `e_flags` from the ELF header
+    pub flags: u32,
+    /// Link-time address of the entry point
+    pub entry: u64,
+    /// ISA string from `.riscv.attributes`
+    pub arch: Option<String>,
     pub sections: Vec<Section>,
     pub functions: Vec<Symbol>,
 }
@@ -80,6 +99,84 @@ fn elf_context(elf: &elf::Elf) -> Ctx {
     }
 }
 
+fn read_uleb128(data: &mut &[u8]) -> Option<u64> {
+    let mut value = 0;
+
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = data.split_first()?;
+        *data = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+fn read_u32(data: &mut &[u8]) -> Option<u32> {
+    let (bytes, rest) = data.split_first_chunk::<4>()?;
+    *data = rest;
+    Some(u32::from_le_bytes(*bytes))
+}
+
+fn read_str<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = data.iter().position(|&b| b == 0)?;
+    let s = &data[..len];
+    *data = &data[len + 1..];
+    Some(s)
+}
+
+/// Finds the ISA string in the contents of `.riscv.attributes`.
+///
+/// Only the attributes of the whole file are looked at. Following the
+/// convention for unknown tags, odd tags have string values and even tags
+/// have ULEB128 values.
+fn parse_arch(data: &[u8]) -> Option<String> {
+    let mut data = data.strip_prefix(b"A")?;
+
+    while !data.is_empty() {
+        let start = data;
+        let len = read_u32(&mut data)? as usize;
+        let mut subsection = start.get(4..len)?;
+        data = &start[len..];
+
+        if read_str(&mut subsection)? != b"riscv" {
+            continue;
+        }
+
+        while !subsection.is_empty() {
+            let start = subsection;
+            let tag = read_uleb128(&mut subsection)?;
+            let tag_len = start.len() - subsection.len();
+            let len = read_u32(&mut subsection)? as usize;
+            let mut attrs = start.get(tag_len + 4..len)?;
+            subsection = &start[len..];
+
+            // Tag_File
+            if tag != 1 {
+                continue;
+            }
+
+            while !attrs.is_empty() {
+                let tag = read_uleb128(&mut attrs)?;
+
+                if tag % 2 == 0 {
+                    read_uleb128(&mut attrs)?;
+                } else {
+                    let value = read_str(&mut attrs)?;
+                    if tag == TAG_RISCV_ARCH {
+                        return String::from_utf8(value.to_owned()).ok();
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
 impl Executable {
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         let elf = elf::Elf::parse(bytes)?;
@@ -106,7 +203,7 @@ impl Executable {
 
             let sym_size = elf::Sym::size(ctx.container);
 
-            if symtab_sh.sh_size as usize % sym_size != 0 {
+            if !(symtab_sh.sh_size as usize).is_multiple_of(sym_size) {
                 bail!(
                     "Invalid symtab size {}, not a multiple of {sym_size}",
                     symtab_sh.sh_size
@@ -130,7 +227,7 @@ impl Executable {
             for reloc in reloc_section {
                 target
                     .entry(reloc.r_offset)
-                    .or_insert(Vec::new())
+                    .or_default()
                     .push(Relocation::from(&symtab, &strtab, &reloc));
             }
         }
@@ -144,9 +241,31 @@ impl Executable {
 
         functions.sort_unstable_by_key(|f| f.addr);
 
+        let arch = sections
+            .iter()
+            .find(|s| s.name == ".riscv.attributes")
+            .and_then(|s| parse_arch(&bytes[s.data.clone()]));
+
         Ok(Self {
+            xlen: if elf.is_64 { Xlen::Rv64 } else { Xlen::Rv32 },
+            flags: elf.header.e_flags,
+            entry: elf.entry,
+            arch,
             sections,
             functions,
         })
     }
+
+    /// Calling convention of the code, from the ELF header.
+    pub fn abi(&self) -> Abi {
+        Abi::new(self.xlen, self.flags & EF_RISCV_RVE != 0)
+    }
+
+    /// Extensions needed to decode the code, from the ISA string.
+    pub fn extensions(&self) -> Extensions {
+        self.arch
+            .as_deref()
+            .map(Extensions::from_arch)
+            .unwrap_or_default()
+    }
 }
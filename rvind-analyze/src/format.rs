@@ -1,40 +1,650 @@
-use rvind_unwinder::Entry;
+use std::{collections::HashMap, mem::size_of};
 
-use crate::analysis::{OrigSpReg, UnwindStep};
+use rvind_unwinder::{
+    compact::{Block, CompactHeader, Rule},
+    container::{self, ContainerHeader, SectionHeader},
+    index::IndexHeader,
+    Entry, ExtEntry, WideEntry, SAVED_REGS,
+};
 
-fn convert(offset: i64, unwind: UnwindStep) -> Option<Entry> {
+use crate::analysis::{saved_regs, OrigSpReg, UnwindStep};
+
+fn convert_sp_reg(sp_reg: OrigSpReg) -> u8 {
+    match sp_reg {
+        OrigSpReg::Sp => 2,
+        OrigSpReg::Fp => 8,
+        OrigSpReg::Other(reg) => reg.number(),
+    }
+}
+
+fn convert_saved(unwind: &UnwindStep) -> [u8; SAVED_REGS] {
+    let mut saved = [ExtEntry::LOST; SAVED_REGS];
+
+    for (slot, reg) in saved.iter_mut().zip(saved_regs()) {
+        *slot = match unwind.saved.get(&reg) {
+            Some(None) => u8::MAX,
+            Some(Some(off)) => match (-off).try_into() {
+                Ok(off) if off < ExtEntry::LOST => off,
+                _ => ExtEntry::LOST,
+            },
+            None => ExtEntry::LOST,
+        };
+    }
+
+    saved
+}
+
+/// Converts the offset of the caller's sp to `sp_offset`, along with
+/// `Entry::FLAG_VLENB` if it has a multiple of `vlenb`.
+fn convert_sp_offset(unwind: &UnwindStep) -> Option<(u32, u8)> {
+    if unwind.sp_vlenb == 0 {
+        return Some((unwind.sp_offset.try_into().ok()?, 0));
+    }
+
+    let offset: u32 = unwind.sp_offset.try_into().ok()?;
+    let scale: u32 = unwind.sp_vlenb.try_into().ok()?;
+
+    if offset >> Entry::VLENB_SHIFT != 0 || scale >> (32 - Entry::VLENB_SHIFT) != 0 {
+        return None;
+    }
+
+    Some((offset | (scale << Entry::VLENB_SHIFT), Entry::FLAG_VLENB))
+}
+
+fn convert(offset: i64, unwind: UnwindStep) -> Option<(ExtEntry, Option<WideEntry>)> {
     let offset: u32 = offset.try_into().ok()?;
+    let (sp_offset, vlenb_flag) = convert_sp_offset(&unwind)?;
+
+    if unwind.trap {
+        let entry = Entry {
+            code_offset: offset,
+            sp_offset,
+            sp_reg: convert_sp_reg(unwind.sp_reg),
+            fp_offset: u8::MAX,
+            ra_offset: u8::MAX,
+            flag: Entry::FLAG_VALID | Entry::FLAG_TRAP | vlenb_flag,
+        };
+
+        let ext = ExtEntry {
+            entry,
+            saved: [u8::MAX; SAVED_REGS],
+            reserved: 0,
+        };
+
+        return Some((ext, None));
+    }
+
+    let convert = |x: Option<i64>| match x {
+        Some(x) => (-x).try_into().ok().filter(|&x| x != u8::MAX),
+        None => Some(u8::MAX),
+    };
+    let convert_wide = |x: Option<i64>| match x {
+        Some(x) => (-x).try_into().ok().filter(|&x| x != WideEntry::IN_REG),
+        None => Some(WideEntry::IN_REG),
+    };
+
+    let (fp_offset, ra_offset, flag, wide) =
+        match (convert(unwind.fp_offset), convert(unwind.ra_offset)) {
+            (Some(fp_offset), Some(ra_offset)) => (fp_offset, ra_offset, Entry::FLAG_VALID, None),
+            _ => {
+                let wide = WideEntry {
+                    code_offset: offset,
+                    fp_offset: convert_wide(unwind.fp_offset)?,
+                    ra_offset: convert_wide(unwind.ra_offset)?,
+                };
+                (0, 0, Entry::FLAG_VALID | Entry::FLAG_WIDE, Some(wide))
+            }
+        };
+
+    let saved = convert_saved(&unwind);
+    let saved_flag = if saved.iter().any(|&off| off != u8::MAX) {
+        Entry::FLAG_SAVED_REGS
+    } else {
+        0
+    };
+
+    let entry = Entry {
+        code_offset: offset,
+        sp_offset,
+        sp_reg: convert_sp_reg(unwind.sp_reg),
+        fp_offset,
+        ra_offset,
+        flag: flag | vlenb_flag | saved_flag,
+    };
+
+    let ext = ExtEntry {
+        entry,
+        saved,
+        reserved: 0,
+    };
+
+    Some((ext, wide))
+}
+
+/// Converts an unwind step to an entry, along with its save offsets if they
+/// only fit in a `WideEntry`.
+pub fn convert_unwind(offset: i64, unwind: Option<UnwindStep>) -> (ExtEntry, Option<WideEntry>) {
+    unwind.and_then(|u| convert(offset, u)).unwrap_or((
+        Entry {
+            code_offset: offset
+                .try_into()
+                .expect("Code offset should not overflow 4 GiB"),
+            sp_offset: 0,
+            sp_reg: 0,
+            fp_offset: 0,
+            ra_offset: 0,
+            flag: 0,
+        }
+        .into(),
+        None,
+    ))
+}
+
+/// Drops the save slots of `s1` through `s11`, along with entries that only
+/// differed from the previous one in those.
+pub fn fixed_entries(entries: &[ExtEntry]) -> Vec<Entry> {
+    let mut fixed: Vec<Entry> = Vec::new();
+
+    for ext in entries {
+        if let Some(last) = fixed.last() {
+            if Rule::from(last) == Rule::from(&ext.entry) {
+                continue;
+            }
+        }
 
-    let convert = |x: Option<i64>| {
-        if let Some(x) = x {
-            (-x).try_into().ok()
+        fixed.push(ext.entry);
+    }
+
+    fixed
+}
+
+/// Number of entries between consecutive blocks of a compact table
+const COMPACT_BLOCK_LEN: usize = 32;
+
+fn write_uleb128(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            out.push(byte);
+            break;
         } else {
-            Some(u8::MAX)
+            out.push(byte | 0x80);
         }
+    }
+}
+
+pub fn encode_compact(entries: &[Entry]) -> Vec<u8> {
+    let mut rules: Vec<Rule> = Vec::new();
+    let mut rule_index: HashMap<Rule, u32> = HashMap::new();
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut stream: Vec<u8> = Vec::new();
+
+    let mut last: Option<(u32, Rule)> = None;
+    let mut block_len = 0;
+
+    for entry in entries {
+        let rule = Rule::from(entry);
+
+        if let Some((_, last_rule)) = last {
+            if rule == last_rule {
+                continue;
+            }
+        }
+
+        let index = *rule_index.entry(rule).or_insert_with(|| {
+            rules.push(rule);
+            (rules.len() - 1).try_into().expect("Rule index overflows")
+        });
+
+        let base = if block_len % COMPACT_BLOCK_LEN == 0 {
+            blocks.push(Block {
+                code_offset: entry.code_offset,
+                stream_offset: stream.len().try_into().expect("Stream offset overflows"),
+            });
+            entry.code_offset
+        } else {
+            last.unwrap().0
+        };
+
+        write_uleb128(&mut stream, entry.code_offset - base);
+        write_uleb128(&mut stream, index);
+
+        last = Some((entry.code_offset, rule));
+        block_len += 1;
+    }
+
+    let header = CompactHeader {
+        rule_count: rules.len().try_into().expect("Rule count overflows"),
+        block_count: blocks.len().try_into().expect("Block count overflows"),
     };
 
-    Some(Entry {
-        code_offset: offset,
-        sp_offset: unwind.sp_offset.try_into().ok()?,
-        sp_reg: match unwind.sp_reg {
-            OrigSpReg::Sp => 2,
-            OrigSpReg::Fp => 8,
-        },
-        fp_offset: convert(unwind.fp_offset)?,
-        ra_offset: convert(unwind.ra_offset)?,
-        flag: 1,
-    })
-}
-
-pub fn convert_unwind(offset: i64, unwind: Option<UnwindStep>) -> Entry {
-    unwind.and_then(|u| convert(offset, u)).unwrap_or(Entry {
-        code_offset: offset
+    let mut data: Vec<u8> = Vec::new();
+    data.extend(header.to_bytes());
+
+    for rule in &rules {
+        data.extend(rule.to_bytes());
+    }
+
+    for block in &blocks {
+        data.extend(block.to_bytes());
+    }
+
+    data.extend(stream);
+    data
+}
+
+/// Text covered by each first-level entry of a page index
+const PAGE_SHIFT: u8 = 12;
+/// Text covered by each second-level entry of a page index
+const BLOCK_SHIFT: u8 = 6;
+
+/// Encodes a page index for a table with entries at `code_offsets`, which must
+/// be sorted.
+pub fn encode_page_index(code_offsets: &[u32], text_len: u32) -> Vec<u8> {
+    let count_at = |offset: u64| code_offsets.partition_point(|&o| o as u64 <= offset);
+
+    let page_size = 1u64 << PAGE_SHIFT;
+    let page_count = (text_len as u64).div_ceil(page_size);
+
+    let header = IndexHeader {
+        page_shift: PAGE_SHIFT,
+        block_shift: BLOCK_SHIFT,
+        reserved: 0,
+        page_count: page_count.try_into().expect("Page count overflows"),
+    };
+
+    let mut pages: Vec<u32> = Vec::new();
+    let mut blocks: Vec<u16> = Vec::new();
+
+    for page in 0..=page_count {
+        let page_start = page * page_size;
+        let page_entries = count_at(page_start);
+        pages.push(page_entries.try_into().expect("Entry count overflows"));
+
+        if page == page_count {
+            break;
+        }
+
+        for block_start in (page_start..page_start + page_size).step_by(1 << BLOCK_SHIFT) {
+            let block_entries = count_at(block_start) - page_entries;
+            blocks.push(block_entries.try_into().expect("Block count overflows"));
+        }
+    }
+
+    let mut data: Vec<u8> = Vec::new();
+    data.extend(header.to_bytes());
+
+    for page in &pages {
+        data.extend(page.to_le_bytes());
+    }
+
+    for block in &blocks {
+        data.extend(block.to_le_bytes());
+    }
+
+    data
+}
+
+fn pad_to_4(data: &mut Vec<u8>) {
+    data.resize(data.len().next_multiple_of(4), 0);
+}
+
+pub fn encode_container(sections: &[(u32, &[u8])]) -> Vec<u8> {
+    let mut directory: Vec<u8> = Vec::new();
+    let mut contents: Vec<u8> = Vec::new();
+
+    let contents_start = size_of::<ContainerHeader>() + sections.len() * size_of::<SectionHeader>();
+
+    for &(kind, data) in sections {
+        let section = SectionHeader {
+            kind,
+            offset: (contents_start + contents.len())
+                .try_into()
+                .expect("Section offset overflows"),
+            len: data.len().try_into().expect("Section length overflows"),
+        };
+        directory.extend(section.to_bytes());
+
+        contents.extend(data);
+        pad_to_4(&mut contents);
+    }
+
+    let mut body = directory;
+    body.extend(contents);
+
+    let header = ContainerHeader {
+        magic: container::MAGIC,
+        version: container::VERSION,
+        total_len: (size_of::<ContainerHeader>() + body.len())
             .try_into()
-            .expect("Code offset should not overflow 4 GiB"),
-        sp_offset: 0,
-        sp_reg: 0,
-        fp_offset: 0,
-        ra_offset: 0,
-        flag: 0,
-    })
+            .expect("Container length overflows"),
+        section_count: sections.len().try_into().expect("Section count overflows"),
+        checksum: container::crc32(&body),
+    };
+
+    let mut data: Vec<u8> = Vec::new();
+    data.extend(header.to_bytes());
+    data.extend(body);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use rvind_unwinder::{
+        compact::CompactTable, container::*, index::PageIndex, Context, Entries, FirstFrame,
+        MemoryReader, Module, Unwinder,
+    };
+
+    use super::*;
+
+    /// Copies `data` to a buffer aligned for the table headers.
+    fn aligned(data: &[u8]) -> (Vec<u8>, usize) {
+        let mut buf = vec![0; data.len() + 8];
+        let start = buf.as_ptr().align_offset(8);
+        buf[start..][..data.len()].copy_from_slice(data);
+        (buf, start)
+    }
+
+    fn entry(code_offset: u32, sp_offset: u32) -> Entry {
+        Entry {
+            code_offset,
+            sp_offset,
+            sp_reg: 2,
+            fp_offset: u8::MAX,
+            ra_offset: u8::MAX,
+            flag: Entry::FLAG_VALID,
+        }
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        // Gaps of up to a few hundred bytes, to need multi-byte ULEB128, and
+        // enough entries for several blocks, with some repeated rules
+        let entries: Vec<Entry> = (0..100u32)
+            .map(|i| entry(i * i * 3 + i, (i % 7) * 16))
+            .collect();
+
+        let data = encode_compact(&entries);
+        let (buf, start) = aligned(&data);
+        let table = CompactTable::from_bytes(&buf[start..][..data.len()]).unwrap();
+
+        // Repeats of the previous rule are merged into the entry before them
+        let mut expected: Vec<&Entry> = Vec::new();
+        for e in &entries {
+            if expected
+                .last()
+                .is_none_or(|l| Rule::from(*l) != Rule::from(e))
+            {
+                expected.push(e);
+            }
+        }
+
+        let last = entries.last().unwrap().code_offset;
+
+        for offset in 0..last + 10 {
+            let want = expected[..expected.partition_point(|e| e.code_offset <= offset)]
+                .last()
+                .copied();
+            let got = table.find(offset);
+
+            assert_eq!(
+                got.map(|e| (e.code_offset, Rule::from(&e))),
+                want.map(|e| (e.code_offset, Rule::from(e))),
+                "at {offset:#x}"
+            );
+        }
+    }
+
+    #[test]
+    fn page_index_round_trip() {
+        // Clustered entries, empty blocks and pages, and entries on page and
+        // block boundaries
+        let offsets = [2, 4, 6, 0x40, 0x7e, 0x1000, 0x2ff0, 0x2ffe, 0x3000, 0x4000];
+        let text_len = 0x4800;
+        let entries: Vec<Entry> = offsets.iter().map(|&o| entry(o, 16)).collect();
+
+        let data = encode_page_index(&offsets, text_len);
+        let (buf, start) = aligned(&data);
+        let index = PageIndex::from_bytes(&buf[start..][..data.len()]).unwrap();
+        let table = Entries::Fixed(&entries);
+
+        assert_eq!(index.entry_count(), offsets.len());
+
+        for offset in 0..text_len + 0x100 {
+            assert_eq!(
+                table
+                    .find_indexed(&index, offset)
+                    .map(|e| e.entry.code_offset),
+                table.find(offset).map(|e| e.entry.code_offset),
+                "at {offset:#x}"
+            );
+        }
+    }
+
+    #[test]
+    fn compact_empty() {
+        let data = encode_compact(&[]);
+        let (buf, start) = aligned(&data);
+        let table = CompactTable::from_bytes(&buf[start..][..data.len()]).unwrap();
+
+        assert!(table.find(0).is_none());
+    }
+
+    fn text() -> TextBounds {
+        TextBounds {
+            start: 0x1000.into(),
+            len: 0x100.into(),
+        }
+    }
+
+    fn unwind_data(entries: &[Entry]) -> Vec<u8> {
+        entries.iter().flat_map(|e| e.to_bytes().to_vec()).collect()
+    }
+
+    /// Parses a container with `sections`, after `change` to its bytes.
+    fn parse_container(
+        sections: &[(u32, &[u8])],
+        change: impl FnOnce(&mut Vec<u8>),
+    ) -> Result<(), TableError> {
+        let mut data = encode_container(sections);
+        change(&mut data);
+        let (buf, start) = aligned(&data);
+        Table::from_bytes(&buf[start..][..data.len()]).map(|_| ())
+    }
+
+    #[test]
+    fn container_round_trip() {
+        let unwind = unwind_data(&[entry(0, 16), entry(0x10, 16), entry(0x40, 16)]);
+        let data =
+            encode_container(&[(SECTION_TEXT, text().to_bytes()), (SECTION_UNWIND, &unwind)]);
+        let (buf, start) = aligned(&data);
+        let table = Table::from_bytes(&buf[start..][..data.len()]).unwrap();
+        let module = table.module(0x100);
+
+        assert_eq!((table.text_start(), table.text_len()), (0x1000, 0x100));
+        assert!(module.contains(0x1100) && !module.contains(0x1000));
+        assert_eq!(table.entries().entry_count(), Some(3));
+        assert!(table.wide().is_empty());
+    }
+
+    #[test]
+    fn container_errors() {
+        let text = text();
+        let unwind = unwind_data(&[entry(0, 16)]);
+        let sections = [
+            (SECTION_TEXT, text.to_bytes()),
+            (SECTION_UNWIND, &unwind[..]),
+        ];
+        let len = encode_container(&sections).len();
+
+        assert!(matches!(
+            parse_container(&sections, |data| *data.last_mut().unwrap() ^= 1),
+            Err(TableError::ChecksumMismatch { .. })
+        ));
+        assert_eq!(
+            parse_container(&sections, |data| data.truncate(len - 4)),
+            Err(TableError::LengthMismatch {
+                expected: len as u32,
+                actual: len - 4,
+            })
+        );
+        assert_eq!(
+            parse_container(&sections, |data| data.truncate(8)),
+            Err(TableError::TooShort)
+        );
+
+        let unsorted = unwind_data(&[entry(0, 16), entry(0x40, 16), entry(0x10, 16)]);
+        assert_eq!(
+            parse_container(
+                &[(SECTION_TEXT, text.to_bytes()), (SECTION_UNWIND, &unsorted),],
+                |_| {}
+            ),
+            Err(TableError::UnsortedEntries)
+        );
+        assert_eq!(
+            parse_container(
+                &[
+                    (SECTION_TEXT, text.to_bytes()),
+                    (SECTION_UNWIND, &unwind),
+                    (SECTION_UNWIND_COMPACT, &[]),
+                ],
+                |_| {}
+            ),
+            Err(TableError::ConflictingSections {
+                first: SECTION_UNWIND,
+                second: SECTION_UNWIND_COMPACT,
+            })
+        );
+    }
+
+    #[test]
+    fn container_bad_compact() {
+        let compact = |blocks: &[Block], stream: &[u8]| {
+            let header = CompactHeader {
+                rule_count: 1,
+                block_count: blocks.len() as u32,
+            };
+
+            let mut data = Vec::new();
+            data.extend(header.to_bytes());
+            data.extend(Rule::from(&entry(0, 16)).to_bytes());
+            blocks.iter().for_each(|b| data.extend(b.to_bytes()));
+            data.extend(stream);
+
+            parse_container(
+                &[
+                    (SECTION_TEXT, text().to_bytes()),
+                    (SECTION_UNWIND_COMPACT, &data),
+                ],
+                |_| {},
+            )
+        };
+        let block = |code_offset, stream_offset| Block {
+            code_offset,
+            stream_offset,
+        };
+        let bad = Err(TableError::BadSection {
+            kind: SECTION_UNWIND_COMPACT,
+        });
+
+        assert_eq!(compact(&[block(0, 0), block(8, 2)], &[0, 0, 0, 0]), Ok(()));
+        // Unknown rule
+        assert_eq!(compact(&[block(0, 0)], &[0, 1]), bad);
+        // Blocks out of order
+        assert_eq!(compact(&[block(8, 0), block(0, 2)], &[0, 0, 0, 0]), bad);
+        // Entry past the start of the next block
+        assert_eq!(
+            compact(&[block(0, 0), block(8, 4)], &[0, 0, 8, 0, 0, 0]),
+            bad
+        );
+    }
+
+    #[test]
+    fn container_wide_entries() {
+        let mut wide_entry = entry(0x40, 16);
+        wide_entry.flag |= Entry::FLAG_WIDE;
+        let entries = [entry(0, 16), wide_entry];
+        let unwind = unwind_data(&entries);
+        let compact = encode_compact(&entries);
+        let wide = WideEntry {
+            code_offset: 0x40,
+            fp_offset: WideEntry::IN_REG,
+            ra_offset: 0x200,
+        };
+
+        let data = encode_container(&[
+            (SECTION_TEXT, text().to_bytes()),
+            (SECTION_UNWIND, &unwind),
+            (SECTION_WIDE, wide.to_bytes()),
+        ]);
+        let (buf, start) = aligned(&data);
+        let table = Table::from_bytes(&buf[start..][..data.len()]).unwrap();
+
+        assert_eq!(table.wide(), [wide]);
+
+        // Entries that need wide entries, in either format, without them
+        for (kind, data) in [
+            (SECTION_UNWIND, &unwind),
+            (SECTION_UNWIND_COMPACT, &compact),
+        ] {
+            assert_eq!(
+                parse_container(&[(SECTION_TEXT, text().to_bytes()), (kind, data)], |_| {}),
+                Err(TableError::MissingWide)
+            );
+        }
+    }
+
+    /// Memory holding `0` except at the given addresses
+    struct Memory(HashMap<usize, usize>);
+
+    impl MemoryReader for Memory {
+        fn read_usize(&self, addr: usize) -> Option<usize> {
+            Some(self.0.get(&addr).copied().unwrap_or(0))
+        }
+    }
+
+    #[test]
+    fn wide_round_trip() {
+        const TEXT: usize = 0x8000;
+
+        // ra saved at the bottom of a 512-byte frame
+        let step = UnwindStep {
+            sp_reg: OrigSpReg::Sp,
+            sp_offset: 0x200,
+            sp_vlenb: 0,
+            fp_offset: None,
+            ra_offset: Some(-0x200),
+            saved: saved_regs().map(|reg| (reg, None)).collect(),
+            trap: false,
+        };
+
+        let (ext, wide) = convert_unwind(0, Some(step));
+        let wide = wide.unwrap();
+
+        assert_ne!(ext.entry.flag & Entry::FLAG_WIDE, 0);
+        assert_eq!((wide.fp_offset, wide.ra_offset), (WideEntry::IN_REG, 0x200));
+
+        let entries = [ext.entry];
+        let wide_entries = [wide];
+        let context = Context {
+            text_start: TEXT,
+            text_len: 0x100,
+        };
+        let mut module = Module::new(&context, &entries[..]);
+        module.wide = &wide_entries;
+
+        let mut regs = [0; 32];
+        regs[0] = TEXT + 4;
+        regs[2] = 0x1000;
+        let memory = Memory([(0x1000, TEXT + 0x10)].into());
+
+        let frames: Vec<(usize, usize)> =
+            Unwinder::new(&module, FirstFrame::from_regs(regs), memory)
+                .map(|frame| frame.map(|f| (f.pc, f.sp)).unwrap())
+                .collect();
+
+        assert_eq!(frames, [(TEXT + 4, 0x1000), (TEXT + 0x10, 0x1200)]);
+    }
 }
@@ -1,25 +1,117 @@
 mod analysis;
+mod callgraph;
+mod coredump;
+mod eh;
 mod elf;
 mod format;
+mod object;
 mod riscv;
 
-use anyhow::{anyhow, Context, Result};
-use clap::Parser;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand};
+use coredump::{Core, CoreMemory};
 use elf::Executable;
-use std::{
-    collections::{BTreeMap, HashMap},
-    ffi::OsString,
-    fs,
+use riscv::{Abi, Extensions};
+use rvind_unwinder::{
+    Checks, Entry, ExtEntry, FirstFrame, FrameSource, Module, Symbol, SymbolTable, Unwinder,
+    WideEntry,
 };
+use std::{collections::BTreeMap, ffi::OsString, fs};
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(short)]
-    output: OsString,
-    file: OsString,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(short, required = true)]
+    output: Option<OsString>,
+    /// Function that saves a trap frame and continues in the trapped context
+    #[arg(long)]
+    trap_entry: Vec<String>,
+    /// Write the unwind table in the compact format
+    #[arg(long)]
+    compact: bool,
+    /// Write all tables into a single container file instead of separate files
+    #[arg(long)]
+    container: bool,
+    /// Write all tables as sections of an object file to link into the image
+    ///
+    /// The object defines `__rvind_{unwind,sym,str,wide}_{start,end}` around
+    /// the `.rvind_unwind`, `.rvind_sym`, `.rvind_str` and `.rvind_wide`
+    /// sections, and `.rvind_bounds` with the start and end of `.text`,
+    /// located from a global function in it when linked. The code in `.text`
+    /// must not change when linking it in, which the `check` command verifies.
+    #[arg(long, conflicts_with_all = ["compact", "container"])]
+    object: bool,
+    /// Write the unwind table in the extended format, which also records
+    /// where s1 through s11 are saved
+    #[arg(long, conflicts_with = "compact")]
+    extended: bool,
+    /// Also write where the LSDA of each function is, from `.eh_frame`, for
+    /// the panic=unwind runtime
+    #[arg(long)]
+    lsda: bool,
+    /// Also write a page index over the unwind table for faster lookups
+    #[arg(long, conflicts_with = "compact")]
+    page_index: bool,
+    /// Calling convention, which decides the registers a call clobbers
+    /// [default: from the ELF header]
+    #[arg(long, value_enum)]
+    abi: Option<Abi>,
+    #[arg(required = true)]
+    file: Option<OsString>,
 }
 
-fn disassemble(mut addr: i64, mut bytes: &[u8], states: &HashMap<i64, analysis::AbstractState>) {
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a backtrace of each thread in a core dump of the executable
+    Core {
+        /// Function that saves a trap frame and continues in the trapped context
+        #[arg(long)]
+        trap_entry: Vec<String>,
+        /// Follow the frame pointer chain through code with no unwind entry
+        #[arg(long)]
+        frame_pointer: bool,
+        /// Calling convention, which decides the registers a call clobbers
+        /// [default: from the ELF header]
+        #[arg(long, value_enum)]
+        abi: Option<Abi>,
+        /// Size of a vector register in bytes, for frames that spill vector
+        /// registers
+        #[arg(long)]
+        vlenb: Option<usize>,
+        file: OsString,
+        core: OsString,
+    },
+    /// Check that linking the object written by `--object` into the image left
+    /// `.text` as it was when the tables were made, and located it
+    Check {
+        /// The executable the tables were made from
+        file: OsString,
+        /// The executable with the object linked in
+        linked: OsString,
+    },
+}
+
+/// Frames to print per thread before giving up
+const MAX_DEPTH: usize = 256;
+
+fn unwind_step(state: &analysis::AbstractState, trap_entry: bool) -> Option<analysis::UnwindStep> {
+    if trap_entry {
+        state.trap_step()
+    } else {
+        state.unwind_step()
+    }
+}
+
+fn disassemble(
+    mut addr: i64,
+    mut bytes: &[u8],
+    abi: Abi,
+    ext: Extensions,
+    result: &analysis::FunctionAnalysis,
+    trap_entry: bool,
+) {
     let range = addr..addr + (bytes.len() as i64);
 
     while let Some(first) = bytes.first() {
@@ -29,11 +121,15 @@ fn disassemble(mut addr: i64, mut bytes: &[u8], states: &HashMap<i64, analysis::
             (2, u16::from_le_bytes(bytes[..2].try_into().unwrap()) as u32)
         };
 
-        let analysis = analysis::analyze_insn(addr, &range, insn);
+        let mut analysis = analysis::analyze_insn(addr, &range, insn, abi, ext);
+
+        if let Some(clobbers) = result.calls.get(&addr) {
+            analysis.clobbers = clobbers.clone();
+        }
 
-        if let Some(state) = states.get(&addr) {
+        if let Some(state) = result.states.get(&addr) {
             println!("{state}");
-            if let Some(uw) = state.unwind_step() {
+            if let Some(uw) = unwind_step(state, trap_entry) {
                 println!("Unwind: {uw}");
             } else {
                 println!("Unwind: (Cannot unwind!)");
@@ -42,7 +138,10 @@ fn disassemble(mut addr: i64, mut bytes: &[u8], states: &HashMap<i64, analysis::
         } else {
             println!("<unreachable?>");
         }
-        println!("  {addr:>#10x}: {}", riscv::disassemble(insn));
+        println!(
+            "  {addr:>#10x}: {}",
+            riscv::disassemble(insn, abi.xlen(), ext)
+        );
         println!("  {:>10}  = {analysis}", "");
         println!();
 
@@ -57,21 +156,47 @@ struct UnwindRange {
     unwind: analysis::UnwindStep,
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let file = &args.file;
+fn read_executable(file: &OsString) -> Result<(Vec<u8>, Executable)> {
     let buf = fs::read(file).context(anyhow!("Cannot read binary file {file:?}"))?;
     let exe = Executable::from_bytes(&buf).context(anyhow!("Failed to parse file {file:?}"))?;
+    Ok((buf, exe))
+}
 
-    let (text_index, text_section) = exe
-        .sections
+fn text_section(exe: &Executable) -> Result<(usize, &elf::Section)> {
+    exe.sections
         .iter()
         .enumerate()
         .find(|(_, s)| s.name == ".text")
-        .ok_or(anyhow!("No .text section found"))?;
+        .ok_or(anyhow!("No .text section found"))
+}
+
+struct Tables {
+    entries: Vec<ExtEntry>,
+    wide: Vec<WideEntry>,
+    symbols: Vec<Symbol>,
+    strings: Vec<u8>,
+}
+
+/// Analyzes every function in `.text`, printing the disassembly and the
+/// unwind ranges if `verbose`.
+fn build_tables(
+    buf: &[u8],
+    exe: &Executable,
+    trap_entry: &[String],
+    abi: Option<Abi>,
+    verbose: bool,
+) -> Result<Tables> {
+    let (text_index, text_section) = text_section(exe)?;
+    let abi = abi.unwrap_or(exe.abi());
+    let ext = exe.extensions();
+
+    if abi.xlen() != exe.xlen {
+        bail!("ABI given with --abi does not match the XLEN of the executable");
+    }
 
     let mut unwind_ranges: Vec<UnwindRange> = Vec::new();
     let mut seen_functions: BTreeMap<u64, elf::Symbol> = BTreeMap::new();
+    let mut text_functions: Vec<(&elf::Symbol, &[u8])> = Vec::new();
 
     for f in &exe.functions {
         if let Some(seen) = seen_functions.get(&f.addr) {
@@ -86,23 +211,39 @@ fn main() -> Result<()> {
             continue;
         }
 
-        let sec = &exe.sections[f.section];
-        let off = (f.addr - sec.addr) as i64;
-        let bytes = &buf[sec.data.clone()][off as usize..(off + f.size as i64) as usize];
-        let state_map = analysis::analyze(f.addr as i64, bytes);
-        println!("{}:", f.name);
-        disassemble(f.addr as i64, bytes, &state_map);
-
-        for (addr, state) in state_map {
-            if let Some(unwind) = state.unwind_step() {
-                let insn_len = if bytes[(addr - f.addr as i64) as usize] & 0b11 == 0b11 {
-                    4
-                } else {
-                    2
-                };
+        let off = (f.addr - text_section.addr) as i64;
+        let bytes = &buf[text_section.data.clone()][off as usize..(off + f.size as i64) as usize];
+        text_functions.push((f, bytes));
+    }
+
+    let mut results = callgraph::analyze_all(
+        text_functions
+            .iter()
+            .map(|&(f, bytes)| (f.addr as i64, bytes)),
+        abi,
+        ext,
+    );
+
+    for (f, bytes) in text_functions {
+        let trap_entry = trap_entry.contains(&f.name);
+        let result = results.remove(&(f.addr as i64)).unwrap();
+
+        if verbose {
+            println!("{}:", f.name);
+            disassemble(f.addr as i64, bytes, abi, ext, &result, trap_entry);
+        }
+
+        for (addr, state) in result.states {
+            // Falling off the end of the function, e.g. after a noreturn call
+            let Some(&first) = bytes.get((addr - f.addr as i64) as usize) else {
+                continue;
+            };
+
+            if let Some(unwind) = unwind_step(&state, trap_entry) {
+                let insn_len = if first & 0b11 == 0b11 { 4 } else { 2 };
                 unwind_ranges.push(UnwindRange {
-                    start: addr - sec.addr as i64,
-                    end: addr - sec.addr as i64 + insn_len,
+                    start: addr - text_section.addr as i64,
+                    end: addr - text_section.addr as i64 + insn_len,
                     unwind,
                 });
             }
@@ -149,25 +290,41 @@ fn main() -> Result<()> {
 
     merged.push((current, None));
 
-    for (start, unwind) in &merged {
-        if let Some(unwind) = unwind {
-            println!("{start:#x} {unwind}");
-        } else {
-            println!("{start:#x} -");
+    if verbose {
+        for (start, unwind) in &merged {
+            if let Some(unwind) = unwind {
+                println!("{start:#x} {unwind}");
+            } else {
+                println!("{start:#x} -");
+            }
         }
     }
 
-    let mut unwind_data: Vec<u8> = Vec::new();
+    let mut entries: Vec<ExtEntry> = Vec::new();
+    let mut wide: Vec<WideEntry> = Vec::new();
 
     for (start, unwind) in merged {
-        let data = format::convert_unwind(start, unwind);
-        unwind_data.extend(data.to_bytes());
-    }
+        let (entry, wide_entry) = format::convert_unwind(start, unwind);
+        let was_wide = entries
+            .last()
+            .is_some_and(|e| (e.entry.flag & Entry::FLAG_WIDE) != 0);
+
+        if let Some(wide_entry) = wide_entry {
+            if !was_wide {
+                eprintln!(
+                    "Using wide save offsets at {:#x?}",
+                    start + text_section.addr as i64
+                );
+            }
 
-    fs::write(&args.output, unwind_data)?;
+            wide.push(wide_entry);
+        }
+
+        entries.push(entry);
+    }
 
-    let mut symtab_data: Vec<u8> = Vec::new();
-    let mut strtab_data: Vec<u8> = Vec::new();
+    let mut symbols: Vec<Symbol> = Vec::new();
+    let mut strings: Vec<u8> = Vec::new();
 
     let mut functions = exe.functions.clone();
     functions.sort_unstable_by_key(|f| f.addr);
@@ -181,28 +338,403 @@ fn main() -> Result<()> {
 
         let sec = &exe.sections[f.section];
         let off: u32 = (f.addr - sec.addr).try_into().expect("Text offset overflows");
-        let str_off: u32 = strtab_data.len().try_into().expect("String offset overflows");
+        let str_off: u32 = strings.len().try_into().expect("String offset overflows");
 
         if last_address == Some(off) {
             continue;
         }
 
-        symtab_data.extend(off.to_le_bytes());
-        symtab_data.extend(str_off.to_le_bytes());
+        symbols.push(Symbol {
+            code_offset: off,
+            name_offset: str_off,
+        });
 
-        strtab_data.extend(rustc_demangle::demangle(&f.name).to_string().as_bytes());
-        strtab_data.extend(b"\0");
+        strings.extend(rustc_demangle::demangle(&f.name).to_string().as_bytes());
+        strings.extend(b"\0");
 
         last_address = Some(off);
     }
 
-    let mut symtab_file = args.output.clone();
+    Ok(Tables {
+        entries,
+        wide,
+        symbols,
+        strings,
+    })
+}
+
+fn backtrace(
+    file: &OsString,
+    core_file: &OsString,
+    trap_entry: &[String],
+    frame_pointer: bool,
+    abi: Option<Abi>,
+    vlenb: Option<usize>,
+) -> Result<()> {
+    let (buf, exe) = read_executable(file)?;
+    let core_buf = fs::read(core_file).context(anyhow!("Cannot read core file {core_file:?}"))?;
+    let core =
+        Core::from_bytes(&core_buf).context(anyhow!("Failed to parse core file {core_file:?}"))?;
+
+    if core.xlen != exe.xlen {
+        bail!("Core file and executable have different XLEN");
+    }
+
+    let (_, text_section) = text_section(&exe)?;
+    let tables = build_tables(&buf, &exe, trap_entry, abi, false)?;
+
+    // Position independent executables may be loaded elsewhere
+    let load_bias = core.entry.map_or(0, |entry| entry.wrapping_sub(exe.entry)) as usize;
+
+    let module = Module {
+        text_start: text_section.addr as usize,
+        text_len: text_section.data.len(),
+        load_bias,
+        entries: tables.entries[..].into(),
+        index: None,
+        wide: &tables.wide,
+    };
+    let symbols = SymbolTable::new(&tables.symbols, &tables.strings);
+    let memory = CoreMemory {
+        core: &core,
+        bytes: &core_buf,
+    };
+
+    for thread in &core.threads {
+        println!("Thread {} (signal {}):", thread.pid, thread.signal);
+
+        let first_frame = FirstFrame::from_regs(thread.regs.map(|r| r as usize));
+
+        let stack = match core.segment(thread.regs[2]) {
+            Some(seg) => seg.addr as usize..(seg.addr + seg.mem_size) as usize,
+            None => 0..0,
+        };
+
+        let mut unwinder = Unwinder::new(&module, first_frame, &memory).checked(Checks {
+            stack,
+            max_depth: MAX_DEPTH,
+        });
+
+        if frame_pointer {
+            unwinder = unwinder.frame_pointer_fallback();
+        }
+
+        if let Some(vlenb) = vlenb {
+            unwinder = unwinder.vlenb(vlenb);
+        }
+
+        for (i, frame) in unwinder.enumerate() {
+            match frame {
+                Ok(frame) => {
+                    let name = match symbols.lookup(&module, frame.pc) {
+                        Some((name, off)) => format!("{name}+{off:#x}"),
+                        None => "??".to_owned(),
+                    };
+                    // Frames from the frame pointer chain may be bogus
+                    let mark = match frame.source {
+                        FrameSource::FramePointer => " [fp]",
+                        FrameSource::Trap => " [trap]",
+                        _ => "",
+                    };
+                    println!(
+                        "  #{i:<3} {:#x} in {name} (sp {:#x}, fp {:#x}){mark}",
+                        frame.pc, frame.sp, frame.fp
+                    );
+                }
+                Err(err) => println!("  Cannot unwind further: {err}"),
+            }
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
+
+fn check_linked(file: &OsString, linked: &OsString) -> Result<()> {
+    let (buf, exe) = read_executable(file)?;
+    let (linked_buf, linked_exe) = read_executable(linked)?;
+    let (_, text) = text_section(&exe)?;
+    let (_, linked_text) = text_section(&linked_exe)?;
+
+    if linked_buf[linked_text.data.clone()] != buf[text.data.clone()] {
+        bail!(".text changed, so the tables do not match it");
+    }
+
+    let bounds = linked_exe
+        .sections
+        .iter()
+        .find(|s| s.name == ".rvind_bounds")
+        .ok_or(anyhow!("The tables are not linked in"))?;
+    let word = linked_exe.xlen.bytes() as usize;
+    let bounds: Vec<u64> = linked_buf[bounds.data.clone()]
+        .chunks_exact(word)
+        .map(|w| w.iter().rev().fold(0, |acc, &b| (acc << 8) | b as u64))
+        .collect();
+    let linked_end = linked_text.addr + linked_text.data.len() as u64;
+
+    if bounds != [linked_text.addr, linked_end] {
+        bail!(
+            ".rvind_bounds does not hold the bounds of .text, {:#x}..{:#x}",
+            linked_text.addr,
+            linked_end
+        );
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    match &args.command {
+        Some(Command::Core {
+            trap_entry,
+            frame_pointer,
+            abi,
+            vlenb,
+            file,
+            core,
+        }) => return backtrace(file, core, trap_entry, *frame_pointer, *abi, *vlenb),
+        Some(Command::Check { file, linked }) => return check_linked(file, linked),
+        None => {}
+    }
+
+    let (Some(output), Some(file)) = (&args.output, &args.file) else {
+        unreachable!("Required arguments missing");
+    };
+
+    let (buf, exe) = read_executable(file)?;
+    let (text_index, text_section) = text_section(&exe)?;
+    let Tables {
+        entries,
+        wide,
+        symbols,
+        strings: strtab_data,
+    } = build_tables(&buf, &exe, &args.trap_entry, args.abi, true)?;
+
+    let fixed = format::fixed_entries(&entries);
+
+    if !args.extended && fixed.iter().any(|e| !matches!(e.sp_reg, 0 | 2 | 8)) {
+        eprintln!(
+            "Some code locates the caller's sp from a register other than sp or fp, \
+            which cannot be unwound below frames that save s1 through s11 without --extended"
+        );
+    }
+
+    if fixed.iter().any(|e| (e.flag & Entry::FLAG_VLENB) != 0) {
+        eprintln!(
+            "Some frames are sized by vlenb, which the unwinder reads from the hart \
+            if built with the V extension, or must be given otherwise"
+        );
+    }
+
+    let mut unwind_data: Vec<u8> = Vec::new();
+
+    for entry in &fixed {
+        unwind_data.extend(entry.to_bytes());
+    }
+
+    if args.compact {
+        let compact_data = format::encode_compact(&fixed);
+
+        if !unwind_data.is_empty() {
+            eprintln!(
+                "Compact table: {} bytes, fixed table: {} bytes, {:.1}% saved",
+                compact_data.len(),
+                unwind_data.len(),
+                100.0 * (1.0 - compact_data.len() as f64 / unwind_data.len() as f64)
+            );
+        }
+
+        unwind_data = compact_data;
+    }
+
+    if args.extended {
+        unwind_data = entries.iter().flat_map(|e| e.to_bytes()).copied().collect();
+    }
+
+    let symtab_data: Vec<u8> = symbols.iter().flat_map(|s| s.to_bytes()).copied().collect();
+    let wide_data: Vec<u8> = wide.iter().flat_map(|w| w.to_bytes()).copied().collect();
+
+    let page_index_data: Option<Vec<u8>> = if args.page_index {
+        let code_offsets: Vec<u32> = if args.extended {
+            entries.iter().map(|e| e.entry.code_offset).collect()
+        } else {
+            fixed.iter().map(|e| e.code_offset).collect()
+        };
+        let text_len = text_section.data.len().try_into()?;
+
+        Some(format::encode_page_index(&code_offsets, text_len))
+    } else {
+        None
+    };
+
+    let lsda_data: Option<Vec<u8>> = if args.lsda {
+        let lsdas = eh::lsdas(&exe, &buf, text_section)?;
+        Some(lsdas.iter().flat_map(|l| l.to_bytes()).copied().collect())
+    } else {
+        None
+    };
+
+    if args.container {
+        use rvind_unwinder::container::*;
+
+        let unwind_kind = if args.compact {
+            SECTION_UNWIND_COMPACT
+        } else if args.extended {
+            SECTION_UNWIND_EXTENDED
+        } else {
+            SECTION_UNWIND
+        };
+
+        let text = TextBounds {
+            start: text_section.addr.into(),
+            len: (text_section.data.len() as u64).into(),
+        };
+
+        let mut sections: Vec<(u32, &[u8])> = vec![
+            (SECTION_TEXT, text.to_bytes()),
+            (unwind_kind, &unwind_data),
+            (SECTION_SYMBOLS, &symtab_data),
+            (SECTION_STRINGS, &strtab_data),
+        ];
+
+        if let Some(data) = &lsda_data {
+            sections.push((SECTION_LSDA, data));
+        }
+
+        if let Some(data) = &page_index_data {
+            sections.push((SECTION_PAGE_INDEX, data));
+        }
+
+        if !wide.is_empty() {
+            sections.push((SECTION_WIDE, &wide_data));
+        }
+
+        let container_data = format::encode_container(&sections);
+
+        fs::write(output, container_data)?;
+
+        return Ok(());
+    }
+
+    if args.object {
+        use object::{DataSection, Reloc};
+
+        let text_end = text_section.addr + text_section.data.len() as u64;
+        let anchor = exe
+            .functions
+            .iter()
+            .find(|f| f.section == text_index && f.global)
+            .ok_or(anyhow!("No global function in .text to locate it by"))?;
+        let bounds = [
+            Reloc {
+                offset: 0,
+                symbol: &anchor.name,
+                addend: text_section.addr.wrapping_sub(anchor.addr) as i64,
+            },
+            Reloc {
+                offset: exe.xlen.bytes() as u64,
+                symbol: &anchor.name,
+                addend: text_end.wrapping_sub(anchor.addr) as i64,
+            },
+        ];
+        let bounds_data = vec![0; 2 * exe.xlen.bytes() as usize];
+
+        let mut sections = vec![
+            DataSection {
+                name: ".rvind_unwind",
+                data: &unwind_data,
+                start_symbol: "__rvind_unwind_start",
+                end_symbol: "__rvind_unwind_end",
+                relocations: &[],
+            },
+            DataSection {
+                name: ".rvind_sym",
+                data: &symtab_data,
+                start_symbol: "__rvind_sym_start",
+                end_symbol: "__rvind_sym_end",
+                relocations: &[],
+            },
+            DataSection {
+                name: ".rvind_str",
+                data: &strtab_data,
+                start_symbol: "__rvind_str_start",
+                end_symbol: "__rvind_str_end",
+                relocations: &[],
+            },
+            // Always present, so that the runtime can link against it
+            DataSection {
+                name: ".rvind_wide",
+                data: &wide_data,
+                start_symbol: "__rvind_wide_start",
+                end_symbol: "__rvind_wide_end",
+                relocations: &[],
+            },
+            DataSection {
+                name: ".rvind_bounds",
+                data: &bounds_data,
+                start_symbol: "__rvind_bounds_start",
+                end_symbol: "__rvind_bounds_end",
+                relocations: &bounds,
+            },
+        ];
+
+        if let Some(data) = &lsda_data {
+            sections.push(DataSection {
+                name: ".rvind_lsda",
+                data,
+                start_symbol: "__rvind_lsda_start",
+                end_symbol: "__rvind_lsda_end",
+                relocations: &[],
+            });
+        }
+
+        if let Some(data) = &page_index_data {
+            sections.push(DataSection {
+                name: ".rvind_index",
+                data,
+                start_symbol: "__rvind_index_start",
+                end_symbol: "__rvind_index_end",
+                relocations: &[],
+            });
+        }
+
+        let object_data = object::write_object(exe.xlen, exe.flags, &sections);
+
+        fs::write(output, object_data)?;
+
+        return Ok(());
+    }
+
+    fs::write(output, unwind_data)?;
+
+    let mut symtab_file = output.clone();
     symtab_file.push(".sym");
-    let mut strtab_file = args.output.clone();
+    let mut strtab_file = output.clone();
     strtab_file.push(".str");
 
     fs::write(symtab_file, symtab_data)?;
     fs::write(strtab_file, strtab_data)?;
 
+    if let Some(data) = lsda_data {
+        let mut lsda_file = output.clone();
+        lsda_file.push(".lsda");
+        fs::write(lsda_file, data)?;
+    }
+
+    if !wide.is_empty() {
+        let mut wide_file = output.clone();
+        wide_file.push(".wide");
+        fs::write(wide_file, wide_data)?;
+    }
+
+    if let Some(data) = page_index_data {
+        let mut page_index_file = output.clone();
+        page_index_file.push(".idx");
+        fs::write(page_index_file, data)?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,412 @@
+//! Writer for relocatable ELF objects that carry the tables as sections.
+
+use std::collections::HashMap;
+
+use crate::riscv::Xlen;
+
+const EM_RISCV: u16 = 243;
+const ET_REL: u16 = 1;
+
+const SHT_PROGBITS: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHT_STRTAB: u32 = 3;
+const SHT_RELA: u32 = 4;
+const SHF_ALLOC: u64 = 2;
+const SHF_INFO_LINK: u64 = 0x40;
+
+const STB_GLOBAL: u8 = 1;
+const STT_NOTYPE: u8 = 0;
+const SHN_UNDEF: u16 = 0;
+
+const R_RISCV_32: u32 = 1;
+const R_RISCV_64: u32 = 2;
+
+pub struct DataSection<'a> {
+    pub name: &'a str,
+    pub data: &'a [u8],
+    /// Names of the symbols marking the start and end of the section
+    pub start_symbol: &'a str,
+    pub end_symbol: &'a str,
+    pub relocations: &'a [Reloc<'a>],
+}
+
+/// A word in a `DataSection` that the linker fills with the address of a
+/// symbol defined elsewhere, plus `addend`.
+pub struct Reloc<'a> {
+    pub offset: u64,
+    pub symbol: &'a str,
+    pub addend: i64,
+}
+
+struct Writer {
+    xlen: Xlen,
+    out: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, v: u8) {
+        self.out.push(v);
+    }
+
+    fn u16(&mut self, v: u16) {
+        self.out.extend(v.to_le_bytes());
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.out.extend(v.to_le_bytes());
+    }
+
+    /// A field that is 32 or 64 bits depending on the ELF class
+    fn word(&mut self, v: u64) {
+        match self.xlen {
+            Xlen::Rv32 => self.u32(v.try_into().expect("Value overflows ELF32 field")),
+            Xlen::Rv64 => self.out.extend(v.to_le_bytes()),
+        }
+    }
+
+    fn align(&mut self, align: usize) {
+        self.out.resize(self.out.len().next_multiple_of(align), 0);
+    }
+}
+
+struct StrTab(Vec<u8>);
+
+impl StrTab {
+    fn new() -> Self {
+        Self(vec![0])
+    }
+
+    fn add(&mut self, name: &str) -> u32 {
+        let off = self.0.len().try_into().expect("String table overflows");
+        self.0.extend(name.as_bytes());
+        self.0.push(0);
+        off
+    }
+}
+
+struct Symbol {
+    name: u32,
+    shndx: u16,
+    value: u64,
+}
+
+/// A relocation with the symbol resolved to its index in `.symtab`
+struct Rela {
+    offset: u64,
+    symbol: usize,
+    addend: i64,
+}
+
+struct SectionHeader {
+    name: u32,
+    ty: u32,
+    flags: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    info: u32,
+    align: u64,
+    entsize: u64,
+}
+
+/// Builds a relocatable object for the RISC-V ELF class given by `xlen`,
+/// with `flags` as `e_flags` so that it links with the analyzed code.
+pub fn write_object(xlen: Xlen, flags: u32, sections: &[DataSection]) -> Vec<u8> {
+    let (ehsize, shentsize, symsize, relasize, r_word) = match xlen {
+        Xlen::Rv32 => (52, 40, 16, 12, R_RISCV_32),
+        Xlen::Rv64 => (64, 64, 24, 24, R_RISCV_64),
+    };
+
+    let mut w = Writer {
+        xlen,
+        out: vec![0; ehsize],
+    };
+
+    let mut shstrtab = StrTab::new();
+    let mut strtab = StrTab::new();
+    let mut headers: Vec<SectionHeader> = vec![SectionHeader {
+        name: 0,
+        ty: 0,
+        flags: 0,
+        offset: 0,
+        size: 0,
+        link: 0,
+        info: 0,
+        align: 0,
+        entsize: 0,
+    }];
+    let mut symbols: Vec<Symbol> = vec![Symbol {
+        name: 0,
+        shndx: 0,
+        value: 0,
+    }];
+    // Index of each section that has relocations, and its relocations
+    let mut relas: Vec<(usize, Vec<Rela>)> = Vec::new();
+    let mut undefined: HashMap<&str, usize> = HashMap::new();
+
+    for sec in sections {
+        // Enough for the words of .rvind_bounds on RV64
+        w.align(8);
+        let shndx: u16 = headers.len().try_into().expect("Too many sections");
+
+        headers.push(SectionHeader {
+            name: shstrtab.add(sec.name),
+            ty: SHT_PROGBITS,
+            flags: SHF_ALLOC,
+            offset: w.out.len() as u64,
+            size: sec.data.len() as u64,
+            link: 0,
+            info: 0,
+            align: 8,
+            entsize: 0,
+        });
+        w.out.extend(sec.data);
+
+        symbols.push(Symbol {
+            name: strtab.add(sec.start_symbol),
+            shndx,
+            value: 0,
+        });
+        symbols.push(Symbol {
+            name: strtab.add(sec.end_symbol),
+            shndx,
+            value: sec.data.len() as u64,
+        });
+
+        if sec.relocations.is_empty() {
+            continue;
+        }
+
+        let mut section_relas = Vec::new();
+
+        for reloc in sec.relocations {
+            // The symbol is undefined here, and resolved by the linker
+            let symbol = *undefined.entry(reloc.symbol).or_insert_with(|| {
+                symbols.push(Symbol {
+                    name: strtab.add(reloc.symbol),
+                    shndx: SHN_UNDEF,
+                    value: 0,
+                });
+                symbols.len() - 1
+            });
+
+            section_relas.push(Rela {
+                offset: reloc.offset,
+                symbol,
+                addend: reloc.addend,
+            });
+        }
+
+        relas.push((shndx as usize, section_relas));
+    }
+
+    // .symtab
+    w.align(8);
+    let symtab_index = headers.len();
+    let symtab_offset = w.out.len() as u64;
+
+    for (i, sym) in symbols.iter().enumerate() {
+        let info = if i == 0 {
+            0
+        } else {
+            (STB_GLOBAL << 4) | STT_NOTYPE
+        };
+
+        w.u32(sym.name);
+        match xlen {
+            Xlen::Rv32 => {
+                w.word(sym.value);
+                w.word(0);
+                w.u8(info);
+                w.u8(0);
+                w.u16(sym.shndx);
+            }
+            Xlen::Rv64 => {
+                w.u8(info);
+                w.u8(0);
+                w.u16(sym.shndx);
+                w.word(sym.value);
+                w.word(0);
+            }
+        }
+    }
+
+    let symtab_size = w.out.len() as u64 - symtab_offset;
+    let symtab_name = shstrtab.add(".symtab");
+    let strtab_name = shstrtab.add(".strtab");
+    let shstrtab_name = shstrtab.add(".shstrtab");
+
+    // The .rela sections come right after .symtab, then .strtab
+    let strtab_index = symtab_index + 1 + relas.len();
+
+    headers.push(SectionHeader {
+        name: symtab_name,
+        ty: SHT_SYMTAB,
+        flags: 0,
+        offset: symtab_offset,
+        size: symtab_size,
+        link: strtab_index as u32,
+        // All symbols but the null symbol are global
+        info: 1,
+        align: 8,
+        entsize: symsize,
+    });
+
+    for (shndx, section_relas) in &relas {
+        let name = format!(".rela{}", sections[shndx - 1].name);
+        let offset = w.out.len() as u64;
+
+        for rela in section_relas {
+            w.word(rela.offset);
+            match xlen {
+                Xlen::Rv32 => {
+                    w.u32(((rela.symbol as u32) << 8) | r_word);
+                    w.u32(rela.addend as i32 as u32);
+                }
+                Xlen::Rv64 => {
+                    w.word(((rela.symbol as u64) << 32) | r_word as u64);
+                    w.word(rela.addend as u64);
+                }
+            }
+        }
+
+        headers.push(SectionHeader {
+            name: shstrtab.add(&name),
+            ty: SHT_RELA,
+            flags: SHF_INFO_LINK,
+            offset,
+            size: w.out.len() as u64 - offset,
+            link: symtab_index as u32,
+            info: *shndx as u32,
+            align: 8,
+            entsize: relasize,
+        });
+    }
+
+    headers.push(SectionHeader {
+        name: strtab_name,
+        ty: SHT_STRTAB,
+        flags: 0,
+        offset: w.out.len() as u64,
+        size: strtab.0.len() as u64,
+        link: 0,
+        info: 0,
+        align: 1,
+        entsize: 0,
+    });
+    w.out.extend(&strtab.0);
+
+    let shstrndx: u16 = headers.len().try_into().expect("Too many sections");
+    headers.push(SectionHeader {
+        name: shstrtab_name,
+        ty: SHT_STRTAB,
+        flags: 0,
+        offset: w.out.len() as u64,
+        size: shstrtab.0.len() as u64,
+        link: 0,
+        info: 0,
+        align: 1,
+        entsize: 0,
+    });
+    w.out.extend(&shstrtab.0);
+
+    w.align(8);
+    let shoff = w.out.len() as u64;
+
+    for sh in &headers {
+        w.u32(sh.name);
+        w.u32(sh.ty);
+        w.word(sh.flags);
+        w.word(0);
+        w.word(sh.offset);
+        w.word(sh.size);
+        w.u32(sh.link);
+        w.u32(sh.info);
+        w.word(sh.align);
+        w.word(sh.entsize);
+    }
+
+    // ELF header, written last now that the section header offset is known
+    let body = std::mem::take(&mut w.out);
+    w.out.extend(b"\x7fELF");
+    w.u8(match xlen {
+        Xlen::Rv32 => 1,
+        Xlen::Rv64 => 2,
+    });
+    w.u8(1); // Little endian
+    w.u8(1); // EV_CURRENT
+    w.align(16);
+    w.u16(ET_REL);
+    w.u16(EM_RISCV);
+    w.u32(1);
+    w.word(0); // e_entry
+    w.word(0); // e_phoff
+    w.word(shoff);
+    w.u32(flags);
+    w.u16(ehsize as u16);
+    w.u16(0);
+    w.u16(0);
+    w.u16(shentsize);
+    w.u16(headers.len().try_into().expect("Too many sections"));
+    w.u16(shstrndx);
+
+    let mut out = w.out;
+    assert_eq!(out.len(), ehsize);
+    out.extend(&body[ehsize..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use goblin::elf::Elf;
+
+    #[test]
+    fn relocations_name_undefined_symbols() {
+        let relocations = [
+            Reloc {
+                offset: 0,
+                symbol: "_start",
+                addend: -0x10,
+            },
+            Reloc {
+                offset: 8,
+                symbol: "_start",
+                addend: 0x100,
+            },
+        ];
+        let data = [0; 16];
+        let object = write_object(
+            Xlen::Rv64,
+            0,
+            &[DataSection {
+                name: ".rvind_bounds",
+                data: &data,
+                start_symbol: "__rvind_bounds_start",
+                end_symbol: "__rvind_bounds_end",
+                relocations: &relocations,
+            }],
+        );
+
+        let elf = Elf::parse(&object).unwrap();
+        let (shndx, relocs) = &elf.shdr_relocs[0];
+        assert_eq!(elf.section_headers[*shndx].sh_info, 1);
+
+        let relocs: Vec<_> = relocs
+            .iter()
+            .map(|r| {
+                let sym = elf.syms.get(r.r_sym).unwrap();
+                let name = elf.strtab.get_at(sym.st_name).unwrap();
+                (r.r_offset, r.r_type, name, sym.st_shndx, r.r_addend)
+            })
+            .collect();
+
+        assert_eq!(
+            relocs,
+            [
+                (0, R_RISCV_64, "_start", 0, Some(-0x10)),
+                (8, R_RISCV_64, "_start", 0, Some(0x100)),
+            ]
+        );
+    }
+}
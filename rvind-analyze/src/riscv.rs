@@ -5,6 +5,93 @@ use lazy_static::lazy_static;
 #[rustfmt::skip]
 pub static REG_NAMES: &[&str] = &["zero","ra","sp","gp","tp","t0","t1","t2","s0","s1","a0","a1","a2","a3","a4","a5","a6","a7","s2","s3","s4","s5","s6","s7","s8","s9","s10","s11","t3","t4","t5","t6"];
 
+#[rustfmt::skip]
+pub static FREG_NAMES: &[&str] = &["ft0","ft1","ft2","ft3","ft4","ft5","ft6","ft7","fs0","fs1","fa0","fa1","fa2","fa3","fa4","fa5","fa6","fa7","fs2","fs3","fs4","fs5","fs6","fs7","fs8","fs9","fs10","fs11","ft8","ft9","ft10","ft11"];
+
+#[rustfmt::skip]
+static RM_NAMES: &[&str] = &["rne", "rtz", "rdn", "rup", "rmm", "rm5", "rm6", "dyn"];
+
+#[rustfmt::skip]
+static VREG_NAMES: &[&str] = &["v0","v1","v2","v3","v4","v5","v6","v7","v8","v9","v10","v11","v12","v13","v14","v15","v16","v17","v18","v19","v20","v21","v22","v23","v24","v25","v26","v27","v28","v29","v30","v31"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Xlen {
+    Rv32,
+    Rv64,
+}
+
+impl Xlen {
+    /// Size of a register in bytes
+    pub fn bytes(self) -> i64 {
+        match self {
+            Xlen::Rv32 => 4,
+            Xlen::Rv64 => 8,
+        }
+    }
+}
+
+/// Integer calling convention, which decides the registers a call clobbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Abi {
+    Ilp32,
+    Ilp32e,
+    Lp64,
+    Lp64e,
+}
+
+impl Abi {
+    /// The standard ABI for `xlen`, or its variant for RV32E/RV64E if `rve`
+    pub fn new(xlen: Xlen, rve: bool) -> Self {
+        match (xlen, rve) {
+            (Xlen::Rv32, false) => Abi::Ilp32,
+            (Xlen::Rv32, true) => Abi::Ilp32e,
+            (Xlen::Rv64, false) => Abi::Lp64,
+            (Xlen::Rv64, true) => Abi::Lp64e,
+        }
+    }
+
+    pub fn xlen(self) -> Xlen {
+        match self {
+            Abi::Ilp32 | Abi::Ilp32e => Xlen::Rv32,
+            Abi::Lp64 | Abi::Lp64e => Xlen::Rv64,
+        }
+    }
+
+    /// Whether only x0 through x15 exist
+    pub fn is_e(self) -> bool {
+        matches!(self, Abi::Ilp32e | Abi::Lp64e)
+    }
+}
+
+/// Extensions that must be known to decode, as their encodings overlap with
+/// those of others
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Extensions {
+    /// Zcmp, which takes the place of c.fld, c.fsd, c.fldsp and c.fsdsp
+    pub zcmp: bool,
+}
+
+impl Extensions {
+    /// Extensions named in an ISA string such as `rv32i2p1_c2p0_zcmp1p0`
+    pub fn from_arch(arch: &str) -> Self {
+        let has = |ext: &str| {
+            arch.split('_').skip(1).any(|part| {
+                // Strip the version, which is a major and an optional minor
+                let part = part.trim_end_matches(|c: char| c.is_ascii_digit());
+                let part = match part.strip_suffix('p') {
+                    Some(major) if major.ends_with(|c: char| c.is_ascii_digit()) => {
+                        major.trim_end_matches(|c: char| c.is_ascii_digit())
+                    }
+                    _ => part,
+                };
+                part == ext
+            })
+        };
+
+        Self { zcmp: has("zcmp") }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Encoding {
     pub name: &'static str,
@@ -69,23 +156,6 @@ static ENCODINGS_32_DATA: &[Encoding] = &[
     Encoding { name: "ecall", mask: 0xffffffff, value: 0x00000073, fields: &[] },
     Encoding { name: "ebreak", mask: 0xffffffff, value: 0x00100073, fields: &[] },
 
-    // rv64_i
-    Encoding { name: "lwu", mask: 0x0000707f, value: 0x00006003, fields: &["rd", "rs1", "imm12"] },
-    Encoding { name: "ld", mask: 0x0000707f, value: 0x00003003, fields: &["rd", "rs1", "imm12"] },
-    Encoding { name: "sd", mask: 0x0000707f, value: 0x00003023, fields: &["rs1", "rs2", "imm12hilo"] },
-    Encoding { name: "slli", mask: 0xfc00707f, value: 0x00001013, fields: &["rd", "rs1", "shamtd"] },
-    Encoding { name: "srli", mask: 0xfc00707f, value: 0x00005013, fields: &["rd", "rs1", "shamtd"] },
-    Encoding { name: "srai", mask: 0xfc00707f, value: 0x40005013, fields: &["rd", "rs1", "shamtd"] },
-    Encoding { name: "addiw", mask: 0x0000707f, value: 0x0000001b, fields: &["rd", "rs1", "imm12"] },
-    Encoding { name: "slliw", mask: 0xfe00707f, value: 0x0000101b, fields: &["rd", "rs1", "shamtw"] },
-    Encoding { name: "srliw", mask: 0xfe00707f, value: 0x0000501b, fields: &["rd", "rs1", "shamtw"] },
-    Encoding { name: "sraiw", mask: 0xfe00707f, value: 0x4000501b, fields: &["rd", "rs1", "shamtw"] },
-    Encoding { name: "addw", mask: 0xfe00707f, value: 0x0000003b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "subw", mask: 0xfe00707f, value: 0x4000003b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "sllw", mask: 0xfe00707f, value: 0x0000103b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "srlw", mask: 0xfe00707f, value: 0x0000503b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "sraw", mask: 0xfe00707f, value: 0x4000503b, fields: &["rd", "rs1", "rs2"] },
-
     // rv_m
     Encoding { name: "mul", mask: 0xfe00707f, value: 0x02000033, fields: &["rd", "rs1", "rs2"] },
     Encoding { name: "mulh", mask: 0xfe00707f, value: 0x02001033, fields: &["rd", "rs1", "rs2"] },
@@ -96,13 +166,6 @@ static ENCODINGS_32_DATA: &[Encoding] = &[
     Encoding { name: "rem", mask: 0xfe00707f, value: 0x02006033, fields: &["rd", "rs1", "rs2"] },
     Encoding { name: "remu", mask: 0xfe00707f, value: 0x02007033, fields: &["rd", "rs1", "rs2"] },
 
-    // rv64_m
-    Encoding { name: "mulw", mask: 0xfe00707f, value: 0x0200003b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "divw", mask: 0xfe00707f, value: 0x0200403b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "divuw", mask: 0xfe00707f, value: 0x0200503b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "remw", mask: 0xfe00707f, value: 0x0200603b, fields: &["rd", "rs1", "rs2"] },
-    Encoding { name: "remuw", mask: 0xfe00707f, value: 0x0200703b, fields: &["rd", "rs1", "rs2"] },
-
     // rv_a
     Encoding { name: "lr.w", mask: 0xf9f0707f, value: 0x1000202f, fields: &["rd", "rs1", /* "aq", "rl" */] },
     Encoding { name: "sc.w", mask: 0xf800707f, value: 0x1800202f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
@@ -116,19 +179,6 @@ static ENCODINGS_32_DATA: &[Encoding] = &[
     Encoding { name: "amominu.w", mask: 0xf800707f, value: 0xc000202f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
     Encoding { name: "amomaxu.w", mask: 0xf800707f, value: 0xe000202f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
 
-    // rv64_a
-    Encoding { name: "lr.d", mask: 0xf9f0707f, value: 0x1000302f, fields: &["rd", "rs1", /* "aq", "rl" */] },
-    Encoding { name: "sc.d", mask: 0xf800707f, value: 0x1800302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amoswap.d", mask: 0xf800707f, value: 0x0800302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amoadd.d", mask: 0xf800707f, value: 0x0000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amoxor.d", mask: 0xf800707f, value: 0x2000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amoand.d", mask: 0xf800707f, value: 0x6000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amoor.d", mask: 0xf800707f, value: 0x4000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amomin.d", mask: 0xf800707f, value: 0x8000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amomax.d", mask: 0xf800707f, value: 0xa000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amominu.d", mask: 0xf800707f, value: 0xc000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-    Encoding { name: "amomaxu.d", mask: 0xf800707f, value: 0xe000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
-
     // rv_zifencei
     Encoding { name: "fence.i", mask: 0x0000707f, value: 0x0000100f, fields: &["rd", "rs1", "imm12"] },
 
@@ -148,6 +198,860 @@ static ENCODINGS_32_DATA: &[Encoding] = &[
     // rv_s
     Encoding { name: "sfence.vma", mask: 0xfe007fff, value: 0x12000073, fields: &["rs1", "rs2"] },
     Encoding { name: "sret", mask: 0xffffffff, value: 0x10200073, fields: &[] },
+
+    // rv_f
+    Encoding { name: "flw", mask: 0x0000707f, value: 0x00002007, fields: &["frd", "rs1", "imm12"] },
+    Encoding { name: "fsw", mask: 0x0000707f, value: 0x00002027, fields: &["rs1", "frs2", "imm12hilo"] },
+    Encoding { name: "fmadd.s", mask: 0x0600007f, value: 0x00000043, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fmsub.s", mask: 0x0600007f, value: 0x00000047, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fnmsub.s", mask: 0x0600007f, value: 0x0000004b, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fnmadd.s", mask: 0x0600007f, value: 0x0000004f, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fadd.s", mask: 0xfe00007f, value: 0x00000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fsub.s", mask: 0xfe00007f, value: 0x08000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fmul.s", mask: 0xfe00007f, value: 0x10000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fdiv.s", mask: 0xfe00007f, value: 0x18000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fsqrt.s", mask: 0xfff0007f, value: 0x58000053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "fsgnj.s", mask: 0xfe00707f, value: 0x20000053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fsgnjn.s", mask: 0xfe00707f, value: 0x20001053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fsgnjx.s", mask: 0xfe00707f, value: 0x20002053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fmin.s", mask: 0xfe00707f, value: 0x28000053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fmax.s", mask: 0xfe00707f, value: 0x28001053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fcvt.w.s", mask: 0xfff0007f, value: 0xc0000053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.wu.s", mask: 0xfff0007f, value: 0xc0100053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.s.w", mask: 0xfff0007f, value: 0xd0000053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fcvt.s.wu", mask: 0xfff0007f, value: 0xd0100053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fmv.x.w", mask: 0xfff0707f, value: 0xe0000053, fields: &["rd", "frs1"] },
+    Encoding { name: "feq.s", mask: 0xfe00707f, value: 0xa0002053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "flt.s", mask: 0xfe00707f, value: 0xa0001053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "fle.s", mask: 0xfe00707f, value: 0xa0000053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "fclass.s", mask: 0xfff0707f, value: 0xe0001053, fields: &["rd", "frs1"] },
+    Encoding { name: "fmv.w.x", mask: 0xfff0707f, value: 0xf0000053, fields: &["frd", "rs1"] },
+
+    // rv_d
+    Encoding { name: "fld", mask: 0x0000707f, value: 0x00003007, fields: &["frd", "rs1", "imm12"] },
+    Encoding { name: "fsd", mask: 0x0000707f, value: 0x00003027, fields: &["rs1", "frs2", "imm12hilo"] },
+    Encoding { name: "fmadd.d", mask: 0x0600007f, value: 0x02000043, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fmsub.d", mask: 0x0600007f, value: 0x02000047, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fnmsub.d", mask: 0x0600007f, value: 0x0200004b, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fnmadd.d", mask: 0x0600007f, value: 0x0200004f, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fadd.d", mask: 0xfe00007f, value: 0x02000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fsub.d", mask: 0xfe00007f, value: 0x0a000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fmul.d", mask: 0xfe00007f, value: 0x12000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fdiv.d", mask: 0xfe00007f, value: 0x1a000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fsqrt.d", mask: 0xfff0007f, value: 0x5a000053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "fsgnj.d", mask: 0xfe00707f, value: 0x22000053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fsgnjn.d", mask: 0xfe00707f, value: 0x22001053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fsgnjx.d", mask: 0xfe00707f, value: 0x22002053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fmin.d", mask: 0xfe00707f, value: 0x2a000053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fmax.d", mask: 0xfe00707f, value: 0x2a001053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fcvt.s.d", mask: 0xfff0007f, value: 0x40100053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "fcvt.d.s", mask: 0xfff0007f, value: 0x42000053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "feq.d", mask: 0xfe00707f, value: 0xa2002053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "flt.d", mask: 0xfe00707f, value: 0xa2001053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "fle.d", mask: 0xfe00707f, value: 0xa2000053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "fclass.d", mask: 0xfff0707f, value: 0xe2001053, fields: &["rd", "frs1"] },
+    Encoding { name: "fcvt.w.d", mask: 0xfff0007f, value: 0xc2000053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.wu.d", mask: 0xfff0007f, value: 0xc2100053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.d.w", mask: 0xfff0007f, value: 0xd2000053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fcvt.d.wu", mask: 0xfff0007f, value: 0xd2100053, fields: &["frd", "rs1", "rm"] },
+
+    // rv_q
+    Encoding { name: "flq", mask: 0x0000707f, value: 0x00004007, fields: &["frd", "rs1", "imm12"] },
+    Encoding { name: "fsq", mask: 0x0000707f, value: 0x00004027, fields: &["rs1", "frs2", "imm12hilo"] },
+    Encoding { name: "fmadd.q", mask: 0x0600007f, value: 0x06000043, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fmsub.q", mask: 0x0600007f, value: 0x06000047, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fnmsub.q", mask: 0x0600007f, value: 0x0600004b, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fnmadd.q", mask: 0x0600007f, value: 0x0600004f, fields: &["frd", "frs1", "frs2", "frs3", "rm"] },
+    Encoding { name: "fadd.q", mask: 0xfe00007f, value: 0x06000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fsub.q", mask: 0xfe00007f, value: 0x0e000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fmul.q", mask: 0xfe00007f, value: 0x16000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fdiv.q", mask: 0xfe00007f, value: 0x1e000053, fields: &["frd", "frs1", "frs2", "rm"] },
+    Encoding { name: "fsqrt.q", mask: 0xfff0007f, value: 0x5e000053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "fsgnj.q", mask: 0xfe00707f, value: 0x26000053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fsgnjn.q", mask: 0xfe00707f, value: 0x26001053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fsgnjx.q", mask: 0xfe00707f, value: 0x26002053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fmin.q", mask: 0xfe00707f, value: 0x2e000053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fmax.q", mask: 0xfe00707f, value: 0x2e001053, fields: &["frd", "frs1", "frs2"] },
+    Encoding { name: "fcvt.s.q", mask: 0xfff0007f, value: 0x40300053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "fcvt.q.s", mask: 0xfff0007f, value: 0x46000053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "fcvt.d.q", mask: 0xfff0007f, value: 0x42300053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "fcvt.q.d", mask: 0xfff0007f, value: 0x46100053, fields: &["frd", "frs1", "rm"] },
+    Encoding { name: "feq.q", mask: 0xfe00707f, value: 0xa6002053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "flt.q", mask: 0xfe00707f, value: 0xa6001053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "fle.q", mask: 0xfe00707f, value: 0xa6000053, fields: &["rd", "frs1", "frs2"] },
+    Encoding { name: "fclass.q", mask: 0xfff0707f, value: 0xe6001053, fields: &["rd", "frs1"] },
+    Encoding { name: "fcvt.w.q", mask: 0xfff0007f, value: 0xc6000053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.wu.q", mask: 0xfff0007f, value: 0xc6100053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.q.w", mask: 0xfff0007f, value: 0xd6000053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fcvt.q.wu", mask: 0xfff0007f, value: 0xd6100053, fields: &["frd", "rs1", "rm"] },
+
+    // rv_v
+    Encoding { name: "vsetivli", mask: 0xc000707f, value: 0xc0007057, fields: &["rd", "zimm", "zimm10"] },
+    Encoding { name: "vsetvli", mask: 0x8000707f, value: 0x00007057, fields: &["rd", "rs1", "zimm11"] },
+    Encoding { name: "vsetvl", mask: 0xfe00707f, value: 0x80007057, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "vle8.v", mask: 0xfdf0707f, value: 0x00000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e8.v", mask: 0xfdf0707f, value: 0x20000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e8.v", mask: 0xfdf0707f, value: 0x40000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e8.v", mask: 0xfdf0707f, value: 0x60000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e8.v", mask: 0xfdf0707f, value: 0x80000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e8.v", mask: 0xfdf0707f, value: 0xa0000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e8.v", mask: 0xfdf0707f, value: 0xc0000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e8.v", mask: 0xfdf0707f, value: 0xe0000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vle16.v", mask: 0xfdf0707f, value: 0x00005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e16.v", mask: 0xfdf0707f, value: 0x20005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e16.v", mask: 0xfdf0707f, value: 0x40005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e16.v", mask: 0xfdf0707f, value: 0x60005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e16.v", mask: 0xfdf0707f, value: 0x80005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e16.v", mask: 0xfdf0707f, value: 0xa0005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e16.v", mask: 0xfdf0707f, value: 0xc0005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e16.v", mask: 0xfdf0707f, value: 0xe0005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vle32.v", mask: 0xfdf0707f, value: 0x00006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e32.v", mask: 0xfdf0707f, value: 0x20006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e32.v", mask: 0xfdf0707f, value: 0x40006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e32.v", mask: 0xfdf0707f, value: 0x60006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e32.v", mask: 0xfdf0707f, value: 0x80006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e32.v", mask: 0xfdf0707f, value: 0xa0006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e32.v", mask: 0xfdf0707f, value: 0xc0006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e32.v", mask: 0xfdf0707f, value: 0xe0006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vle64.v", mask: 0xfdf0707f, value: 0x00007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e64.v", mask: 0xfdf0707f, value: 0x20007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e64.v", mask: 0xfdf0707f, value: 0x40007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e64.v", mask: 0xfdf0707f, value: 0x60007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e64.v", mask: 0xfdf0707f, value: 0x80007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e64.v", mask: 0xfdf0707f, value: 0xa0007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e64.v", mask: 0xfdf0707f, value: 0xc0007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e64.v", mask: 0xfdf0707f, value: 0xe0007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vle8ff.v", mask: 0xfdf0707f, value: 0x01000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e8ff.v", mask: 0xfdf0707f, value: 0x21000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e8ff.v", mask: 0xfdf0707f, value: 0x41000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e8ff.v", mask: 0xfdf0707f, value: 0x61000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e8ff.v", mask: 0xfdf0707f, value: 0x81000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e8ff.v", mask: 0xfdf0707f, value: 0xa1000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e8ff.v", mask: 0xfdf0707f, value: 0xc1000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e8ff.v", mask: 0xfdf0707f, value: 0xe1000007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vle16ff.v", mask: 0xfdf0707f, value: 0x01005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e16ff.v", mask: 0xfdf0707f, value: 0x21005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e16ff.v", mask: 0xfdf0707f, value: 0x41005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e16ff.v", mask: 0xfdf0707f, value: 0x61005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e16ff.v", mask: 0xfdf0707f, value: 0x81005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e16ff.v", mask: 0xfdf0707f, value: 0xa1005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e16ff.v", mask: 0xfdf0707f, value: 0xc1005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e16ff.v", mask: 0xfdf0707f, value: 0xe1005007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vle32ff.v", mask: 0xfdf0707f, value: 0x01006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e32ff.v", mask: 0xfdf0707f, value: 0x21006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e32ff.v", mask: 0xfdf0707f, value: 0x41006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e32ff.v", mask: 0xfdf0707f, value: 0x61006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e32ff.v", mask: 0xfdf0707f, value: 0x81006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e32ff.v", mask: 0xfdf0707f, value: 0xa1006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e32ff.v", mask: 0xfdf0707f, value: 0xc1006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e32ff.v", mask: 0xfdf0707f, value: 0xe1006007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vle64ff.v", mask: 0xfdf0707f, value: 0x01007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg2e64ff.v", mask: 0xfdf0707f, value: 0x21007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg3e64ff.v", mask: 0xfdf0707f, value: 0x41007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg4e64ff.v", mask: 0xfdf0707f, value: 0x61007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg5e64ff.v", mask: 0xfdf0707f, value: 0x81007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg6e64ff.v", mask: 0xfdf0707f, value: 0xa1007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg7e64ff.v", mask: 0xfdf0707f, value: 0xc1007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vlseg8e64ff.v", mask: 0xfdf0707f, value: 0xe1007007, fields: &["vd", "rs1", /* "vm" */] },
+    Encoding { name: "vl1re8.v", mask: 0xfff0707f, value: 0x02800007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl1re16.v", mask: 0xfff0707f, value: 0x02805007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl1re32.v", mask: 0xfff0707f, value: 0x02806007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl1re64.v", mask: 0xfff0707f, value: 0x02807007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl2re8.v", mask: 0xfff0707f, value: 0x22800007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl2re16.v", mask: 0xfff0707f, value: 0x22805007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl2re32.v", mask: 0xfff0707f, value: 0x22806007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl2re64.v", mask: 0xfff0707f, value: 0x22807007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl4re8.v", mask: 0xfff0707f, value: 0x62800007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl4re16.v", mask: 0xfff0707f, value: 0x62805007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl4re32.v", mask: 0xfff0707f, value: 0x62806007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl4re64.v", mask: 0xfff0707f, value: 0x62807007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl8re8.v", mask: 0xfff0707f, value: 0xe2800007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl8re16.v", mask: 0xfff0707f, value: 0xe2805007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl8re32.v", mask: 0xfff0707f, value: 0xe2806007, fields: &["vd", "rs1"] },
+    Encoding { name: "vl8re64.v", mask: 0xfff0707f, value: 0xe2807007, fields: &["vd", "rs1"] },
+    Encoding { name: "vlm.v", mask: 0xfff0707f, value: 0x02b00007, fields: &["vd", "rs1"] },
+    Encoding { name: "vlse8.v", mask: 0xfc00707f, value: 0x08000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg2e8.v", mask: 0xfc00707f, value: 0x28000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg3e8.v", mask: 0xfc00707f, value: 0x48000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg4e8.v", mask: 0xfc00707f, value: 0x68000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg5e8.v", mask: 0xfc00707f, value: 0x88000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg6e8.v", mask: 0xfc00707f, value: 0xa8000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg7e8.v", mask: 0xfc00707f, value: 0xc8000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg8e8.v", mask: 0xfc00707f, value: 0xe8000007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlse16.v", mask: 0xfc00707f, value: 0x08005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg2e16.v", mask: 0xfc00707f, value: 0x28005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg3e16.v", mask: 0xfc00707f, value: 0x48005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg4e16.v", mask: 0xfc00707f, value: 0x68005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg5e16.v", mask: 0xfc00707f, value: 0x88005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg6e16.v", mask: 0xfc00707f, value: 0xa8005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg7e16.v", mask: 0xfc00707f, value: 0xc8005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg8e16.v", mask: 0xfc00707f, value: 0xe8005007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlse32.v", mask: 0xfc00707f, value: 0x08006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg2e32.v", mask: 0xfc00707f, value: 0x28006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg3e32.v", mask: 0xfc00707f, value: 0x48006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg4e32.v", mask: 0xfc00707f, value: 0x68006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg5e32.v", mask: 0xfc00707f, value: 0x88006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg6e32.v", mask: 0xfc00707f, value: 0xa8006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg7e32.v", mask: 0xfc00707f, value: 0xc8006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg8e32.v", mask: 0xfc00707f, value: 0xe8006007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlse64.v", mask: 0xfc00707f, value: 0x08007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg2e64.v", mask: 0xfc00707f, value: 0x28007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg3e64.v", mask: 0xfc00707f, value: 0x48007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg4e64.v", mask: 0xfc00707f, value: 0x68007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg5e64.v", mask: 0xfc00707f, value: 0x88007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg6e64.v", mask: 0xfc00707f, value: 0xa8007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg7e64.v", mask: 0xfc00707f, value: 0xc8007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vlsseg8e64.v", mask: 0xfc00707f, value: 0xe8007007, fields: &["vd", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vluxei8.v", mask: 0xfc00707f, value: 0x04000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg2ei8.v", mask: 0xfc00707f, value: 0x24000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg3ei8.v", mask: 0xfc00707f, value: 0x44000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg4ei8.v", mask: 0xfc00707f, value: 0x64000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg5ei8.v", mask: 0xfc00707f, value: 0x84000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg6ei8.v", mask: 0xfc00707f, value: 0xa4000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg7ei8.v", mask: 0xfc00707f, value: 0xc4000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg8ei8.v", mask: 0xfc00707f, value: 0xe4000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxei16.v", mask: 0xfc00707f, value: 0x04005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg2ei16.v", mask: 0xfc00707f, value: 0x24005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg3ei16.v", mask: 0xfc00707f, value: 0x44005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg4ei16.v", mask: 0xfc00707f, value: 0x64005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg5ei16.v", mask: 0xfc00707f, value: 0x84005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg6ei16.v", mask: 0xfc00707f, value: 0xa4005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg7ei16.v", mask: 0xfc00707f, value: 0xc4005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg8ei16.v", mask: 0xfc00707f, value: 0xe4005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxei32.v", mask: 0xfc00707f, value: 0x04006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg2ei32.v", mask: 0xfc00707f, value: 0x24006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg3ei32.v", mask: 0xfc00707f, value: 0x44006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg4ei32.v", mask: 0xfc00707f, value: 0x64006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg5ei32.v", mask: 0xfc00707f, value: 0x84006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg6ei32.v", mask: 0xfc00707f, value: 0xa4006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg7ei32.v", mask: 0xfc00707f, value: 0xc4006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg8ei32.v", mask: 0xfc00707f, value: 0xe4006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxei64.v", mask: 0xfc00707f, value: 0x04007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg2ei64.v", mask: 0xfc00707f, value: 0x24007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg3ei64.v", mask: 0xfc00707f, value: 0x44007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg4ei64.v", mask: 0xfc00707f, value: 0x64007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg5ei64.v", mask: 0xfc00707f, value: 0x84007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg6ei64.v", mask: 0xfc00707f, value: 0xa4007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg7ei64.v", mask: 0xfc00707f, value: 0xc4007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vluxseg8ei64.v", mask: 0xfc00707f, value: 0xe4007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxei8.v", mask: 0xfc00707f, value: 0x0c000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg2ei8.v", mask: 0xfc00707f, value: 0x2c000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg3ei8.v", mask: 0xfc00707f, value: 0x4c000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg4ei8.v", mask: 0xfc00707f, value: 0x6c000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg5ei8.v", mask: 0xfc00707f, value: 0x8c000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg6ei8.v", mask: 0xfc00707f, value: 0xac000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg7ei8.v", mask: 0xfc00707f, value: 0xcc000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg8ei8.v", mask: 0xfc00707f, value: 0xec000007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxei16.v", mask: 0xfc00707f, value: 0x0c005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg2ei16.v", mask: 0xfc00707f, value: 0x2c005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg3ei16.v", mask: 0xfc00707f, value: 0x4c005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg4ei16.v", mask: 0xfc00707f, value: 0x6c005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg5ei16.v", mask: 0xfc00707f, value: 0x8c005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg6ei16.v", mask: 0xfc00707f, value: 0xac005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg7ei16.v", mask: 0xfc00707f, value: 0xcc005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg8ei16.v", mask: 0xfc00707f, value: 0xec005007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxei32.v", mask: 0xfc00707f, value: 0x0c006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg2ei32.v", mask: 0xfc00707f, value: 0x2c006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg3ei32.v", mask: 0xfc00707f, value: 0x4c006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg4ei32.v", mask: 0xfc00707f, value: 0x6c006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg5ei32.v", mask: 0xfc00707f, value: 0x8c006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg6ei32.v", mask: 0xfc00707f, value: 0xac006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg7ei32.v", mask: 0xfc00707f, value: 0xcc006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg8ei32.v", mask: 0xfc00707f, value: 0xec006007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxei64.v", mask: 0xfc00707f, value: 0x0c007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg2ei64.v", mask: 0xfc00707f, value: 0x2c007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg3ei64.v", mask: 0xfc00707f, value: 0x4c007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg4ei64.v", mask: 0xfc00707f, value: 0x6c007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg5ei64.v", mask: 0xfc00707f, value: 0x8c007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg6ei64.v", mask: 0xfc00707f, value: 0xac007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg7ei64.v", mask: 0xfc00707f, value: 0xcc007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vloxseg8ei64.v", mask: 0xfc00707f, value: 0xec007007, fields: &["vd", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vse8.v", mask: 0xfdf0707f, value: 0x00000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg2e8.v", mask: 0xfdf0707f, value: 0x20000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg3e8.v", mask: 0xfdf0707f, value: 0x40000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg4e8.v", mask: 0xfdf0707f, value: 0x60000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg5e8.v", mask: 0xfdf0707f, value: 0x80000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg6e8.v", mask: 0xfdf0707f, value: 0xa0000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg7e8.v", mask: 0xfdf0707f, value: 0xc0000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg8e8.v", mask: 0xfdf0707f, value: 0xe0000027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vse16.v", mask: 0xfdf0707f, value: 0x00005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg2e16.v", mask: 0xfdf0707f, value: 0x20005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg3e16.v", mask: 0xfdf0707f, value: 0x40005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg4e16.v", mask: 0xfdf0707f, value: 0x60005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg5e16.v", mask: 0xfdf0707f, value: 0x80005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg6e16.v", mask: 0xfdf0707f, value: 0xa0005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg7e16.v", mask: 0xfdf0707f, value: 0xc0005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg8e16.v", mask: 0xfdf0707f, value: 0xe0005027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vse32.v", mask: 0xfdf0707f, value: 0x00006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg2e32.v", mask: 0xfdf0707f, value: 0x20006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg3e32.v", mask: 0xfdf0707f, value: 0x40006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg4e32.v", mask: 0xfdf0707f, value: 0x60006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg5e32.v", mask: 0xfdf0707f, value: 0x80006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg6e32.v", mask: 0xfdf0707f, value: 0xa0006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg7e32.v", mask: 0xfdf0707f, value: 0xc0006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg8e32.v", mask: 0xfdf0707f, value: 0xe0006027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vse64.v", mask: 0xfdf0707f, value: 0x00007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg2e64.v", mask: 0xfdf0707f, value: 0x20007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg3e64.v", mask: 0xfdf0707f, value: 0x40007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg4e64.v", mask: 0xfdf0707f, value: 0x60007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg5e64.v", mask: 0xfdf0707f, value: 0x80007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg6e64.v", mask: 0xfdf0707f, value: 0xa0007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg7e64.v", mask: 0xfdf0707f, value: 0xc0007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vsseg8e64.v", mask: 0xfdf0707f, value: 0xe0007027, fields: &["vs3", "rs1", /* "vm" */] },
+    Encoding { name: "vs1r.v", mask: 0xfff0707f, value: 0x02800027, fields: &["vs3", "rs1"] },
+    Encoding { name: "vs2r.v", mask: 0xfff0707f, value: 0x22800027, fields: &["vs3", "rs1"] },
+    Encoding { name: "vs4r.v", mask: 0xfff0707f, value: 0x62800027, fields: &["vs3", "rs1"] },
+    Encoding { name: "vs8r.v", mask: 0xfff0707f, value: 0xe2800027, fields: &["vs3", "rs1"] },
+    Encoding { name: "vsm.v", mask: 0xfff0707f, value: 0x02b00027, fields: &["vs3", "rs1"] },
+    Encoding { name: "vsse8.v", mask: 0xfc00707f, value: 0x08000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg2e8.v", mask: 0xfc00707f, value: 0x28000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg3e8.v", mask: 0xfc00707f, value: 0x48000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg4e8.v", mask: 0xfc00707f, value: 0x68000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg5e8.v", mask: 0xfc00707f, value: 0x88000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg6e8.v", mask: 0xfc00707f, value: 0xa8000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg7e8.v", mask: 0xfc00707f, value: 0xc8000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg8e8.v", mask: 0xfc00707f, value: 0xe8000027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vsse16.v", mask: 0xfc00707f, value: 0x08005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg2e16.v", mask: 0xfc00707f, value: 0x28005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg3e16.v", mask: 0xfc00707f, value: 0x48005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg4e16.v", mask: 0xfc00707f, value: 0x68005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg5e16.v", mask: 0xfc00707f, value: 0x88005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg6e16.v", mask: 0xfc00707f, value: 0xa8005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg7e16.v", mask: 0xfc00707f, value: 0xc8005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg8e16.v", mask: 0xfc00707f, value: 0xe8005027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vsse32.v", mask: 0xfc00707f, value: 0x08006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg2e32.v", mask: 0xfc00707f, value: 0x28006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg3e32.v", mask: 0xfc00707f, value: 0x48006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg4e32.v", mask: 0xfc00707f, value: 0x68006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg5e32.v", mask: 0xfc00707f, value: 0x88006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg6e32.v", mask: 0xfc00707f, value: 0xa8006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg7e32.v", mask: 0xfc00707f, value: 0xc8006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg8e32.v", mask: 0xfc00707f, value: 0xe8006027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vsse64.v", mask: 0xfc00707f, value: 0x08007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg2e64.v", mask: 0xfc00707f, value: 0x28007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg3e64.v", mask: 0xfc00707f, value: 0x48007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg4e64.v", mask: 0xfc00707f, value: 0x68007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg5e64.v", mask: 0xfc00707f, value: 0x88007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg6e64.v", mask: 0xfc00707f, value: 0xa8007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg7e64.v", mask: 0xfc00707f, value: 0xc8007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vssseg8e64.v", mask: 0xfc00707f, value: 0xe8007027, fields: &["vs3", "rs1", "rs2", /* "vm" */] },
+    Encoding { name: "vsuxei8.v", mask: 0xfc00707f, value: 0x04000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg2ei8.v", mask: 0xfc00707f, value: 0x24000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg3ei8.v", mask: 0xfc00707f, value: 0x44000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg4ei8.v", mask: 0xfc00707f, value: 0x64000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg5ei8.v", mask: 0xfc00707f, value: 0x84000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg6ei8.v", mask: 0xfc00707f, value: 0xa4000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg7ei8.v", mask: 0xfc00707f, value: 0xc4000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg8ei8.v", mask: 0xfc00707f, value: 0xe4000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxei16.v", mask: 0xfc00707f, value: 0x04005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg2ei16.v", mask: 0xfc00707f, value: 0x24005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg3ei16.v", mask: 0xfc00707f, value: 0x44005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg4ei16.v", mask: 0xfc00707f, value: 0x64005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg5ei16.v", mask: 0xfc00707f, value: 0x84005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg6ei16.v", mask: 0xfc00707f, value: 0xa4005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg7ei16.v", mask: 0xfc00707f, value: 0xc4005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg8ei16.v", mask: 0xfc00707f, value: 0xe4005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxei32.v", mask: 0xfc00707f, value: 0x04006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg2ei32.v", mask: 0xfc00707f, value: 0x24006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg3ei32.v", mask: 0xfc00707f, value: 0x44006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg4ei32.v", mask: 0xfc00707f, value: 0x64006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg5ei32.v", mask: 0xfc00707f, value: 0x84006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg6ei32.v", mask: 0xfc00707f, value: 0xa4006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg7ei32.v", mask: 0xfc00707f, value: 0xc4006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg8ei32.v", mask: 0xfc00707f, value: 0xe4006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxei64.v", mask: 0xfc00707f, value: 0x04007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg2ei64.v", mask: 0xfc00707f, value: 0x24007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg3ei64.v", mask: 0xfc00707f, value: 0x44007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg4ei64.v", mask: 0xfc00707f, value: 0x64007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg5ei64.v", mask: 0xfc00707f, value: 0x84007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg6ei64.v", mask: 0xfc00707f, value: 0xa4007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg7ei64.v", mask: 0xfc00707f, value: 0xc4007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsuxseg8ei64.v", mask: 0xfc00707f, value: 0xe4007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxei8.v", mask: 0xfc00707f, value: 0x0c000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg2ei8.v", mask: 0xfc00707f, value: 0x2c000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg3ei8.v", mask: 0xfc00707f, value: 0x4c000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg4ei8.v", mask: 0xfc00707f, value: 0x6c000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg5ei8.v", mask: 0xfc00707f, value: 0x8c000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg6ei8.v", mask: 0xfc00707f, value: 0xac000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg7ei8.v", mask: 0xfc00707f, value: 0xcc000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg8ei8.v", mask: 0xfc00707f, value: 0xec000027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxei16.v", mask: 0xfc00707f, value: 0x0c005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg2ei16.v", mask: 0xfc00707f, value: 0x2c005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg3ei16.v", mask: 0xfc00707f, value: 0x4c005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg4ei16.v", mask: 0xfc00707f, value: 0x6c005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg5ei16.v", mask: 0xfc00707f, value: 0x8c005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg6ei16.v", mask: 0xfc00707f, value: 0xac005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg7ei16.v", mask: 0xfc00707f, value: 0xcc005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg8ei16.v", mask: 0xfc00707f, value: 0xec005027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxei32.v", mask: 0xfc00707f, value: 0x0c006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg2ei32.v", mask: 0xfc00707f, value: 0x2c006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg3ei32.v", mask: 0xfc00707f, value: 0x4c006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg4ei32.v", mask: 0xfc00707f, value: 0x6c006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg5ei32.v", mask: 0xfc00707f, value: 0x8c006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg6ei32.v", mask: 0xfc00707f, value: 0xac006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg7ei32.v", mask: 0xfc00707f, value: 0xcc006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg8ei32.v", mask: 0xfc00707f, value: 0xec006027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxei64.v", mask: 0xfc00707f, value: 0x0c007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg2ei64.v", mask: 0xfc00707f, value: 0x2c007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg3ei64.v", mask: 0xfc00707f, value: 0x4c007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg4ei64.v", mask: 0xfc00707f, value: 0x6c007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg5ei64.v", mask: 0xfc00707f, value: 0x8c007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg6ei64.v", mask: 0xfc00707f, value: 0xac007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg7ei64.v", mask: 0xfc00707f, value: 0xcc007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+    Encoding { name: "vsoxseg8ei64.v", mask: 0xfc00707f, value: 0xec007027, fields: &["vs3", "rs1", "vs2", /* "vm" */] },
+
+    Encoding { name: "vadd.vv", mask: 0xfc00707f, value: 0x00000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vadd.vx", mask: 0xfc00707f, value: 0x00004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vadd.vi", mask: 0xfc00707f, value: 0x00003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vsub.vv", mask: 0xfc00707f, value: 0x08000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vsub.vx", mask: 0xfc00707f, value: 0x08004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vrsub.vx", mask: 0xfc00707f, value: 0x0c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vrsub.vi", mask: 0xfc00707f, value: 0x0c003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vminu.vv", mask: 0xfc00707f, value: 0x10000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vminu.vx", mask: 0xfc00707f, value: 0x10004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmin.vv", mask: 0xfc00707f, value: 0x14000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmin.vx", mask: 0xfc00707f, value: 0x14004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmaxu.vv", mask: 0xfc00707f, value: 0x18000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmaxu.vx", mask: 0xfc00707f, value: 0x18004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmax.vv", mask: 0xfc00707f, value: 0x1c000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmax.vx", mask: 0xfc00707f, value: 0x1c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vand.vv", mask: 0xfc00707f, value: 0x24000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vand.vx", mask: 0xfc00707f, value: 0x24004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vand.vi", mask: 0xfc00707f, value: 0x24003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vor.vv", mask: 0xfc00707f, value: 0x28000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vor.vx", mask: 0xfc00707f, value: 0x28004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vor.vi", mask: 0xfc00707f, value: 0x28003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vxor.vv", mask: 0xfc00707f, value: 0x2c000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vxor.vx", mask: 0xfc00707f, value: 0x2c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vxor.vi", mask: 0xfc00707f, value: 0x2c003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vrgather.vv", mask: 0xfc00707f, value: 0x30000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vrgather.vx", mask: 0xfc00707f, value: 0x30004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vrgather.vi", mask: 0xfc00707f, value: 0x30003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vrgatherei16.vv", mask: 0xfc00707f, value: 0x38000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vslideup.vx", mask: 0xfc00707f, value: 0x38004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vslideup.vi", mask: 0xfc00707f, value: 0x38003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vslidedown.vx", mask: 0xfc00707f, value: 0x3c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vslidedown.vi", mask: 0xfc00707f, value: 0x3c003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vadc.vvm", mask: 0xfe00707f, value: 0x40000057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vadc.vxm", mask: 0xfe00707f, value: 0x40004057, fields: &["vd", "vs2", "rs1"] },
+    Encoding { name: "vadc.vim", mask: 0xfe00707f, value: 0x40003057, fields: &["vd", "vs2", "simm5"] },
+    Encoding { name: "vmadc.vvm", mask: 0xfe00707f, value: 0x44000057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmadc.vxm", mask: 0xfe00707f, value: 0x44004057, fields: &["vd", "vs2", "rs1"] },
+    Encoding { name: "vmadc.vim", mask: 0xfe00707f, value: 0x44003057, fields: &["vd", "vs2", "simm5"] },
+    Encoding { name: "vmadc.vv", mask: 0xfe00707f, value: 0x46000057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmadc.vx", mask: 0xfe00707f, value: 0x46004057, fields: &["vd", "vs2", "rs1"] },
+    Encoding { name: "vmadc.vi", mask: 0xfe00707f, value: 0x46003057, fields: &["vd", "vs2", "simm5"] },
+    Encoding { name: "vsbc.vvm", mask: 0xfe00707f, value: 0x48000057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vsbc.vxm", mask: 0xfe00707f, value: 0x48004057, fields: &["vd", "vs2", "rs1"] },
+    Encoding { name: "vmsbc.vvm", mask: 0xfe00707f, value: 0x4c000057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmsbc.vxm", mask: 0xfe00707f, value: 0x4c004057, fields: &["vd", "vs2", "rs1"] },
+    Encoding { name: "vmsbc.vv", mask: 0xfe00707f, value: 0x4e000057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmsbc.vx", mask: 0xfe00707f, value: 0x4e004057, fields: &["vd", "vs2", "rs1"] },
+    Encoding { name: "vmerge.vvm", mask: 0xfe00707f, value: 0x5c000057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmerge.vxm", mask: 0xfe00707f, value: 0x5c004057, fields: &["vd", "vs2", "rs1"] },
+    Encoding { name: "vmerge.vim", mask: 0xfe00707f, value: 0x5c003057, fields: &["vd", "vs2", "simm5"] },
+    Encoding { name: "vmv.v.v", mask: 0xfff0707f, value: 0x5e000057, fields: &["vd", "vs1"] },
+    Encoding { name: "vmv.v.x", mask: 0xfff0707f, value: 0x5e004057, fields: &["vd", "rs1"] },
+    Encoding { name: "vmv.v.i", mask: 0xfff0707f, value: 0x5e003057, fields: &["vd", "simm5"] },
+    Encoding { name: "vmseq.vv", mask: 0xfc00707f, value: 0x60000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmseq.vx", mask: 0xfc00707f, value: 0x60004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmseq.vi", mask: 0xfc00707f, value: 0x60003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vmsne.vv", mask: 0xfc00707f, value: 0x64000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmsne.vx", mask: 0xfc00707f, value: 0x64004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmsne.vi", mask: 0xfc00707f, value: 0x64003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vmsltu.vv", mask: 0xfc00707f, value: 0x68000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmsltu.vx", mask: 0xfc00707f, value: 0x68004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmslt.vv", mask: 0xfc00707f, value: 0x6c000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmslt.vx", mask: 0xfc00707f, value: 0x6c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmsleu.vv", mask: 0xfc00707f, value: 0x70000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmsleu.vx", mask: 0xfc00707f, value: 0x70004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmsleu.vi", mask: 0xfc00707f, value: 0x70003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vmsle.vv", mask: 0xfc00707f, value: 0x74000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmsle.vx", mask: 0xfc00707f, value: 0x74004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmsle.vi", mask: 0xfc00707f, value: 0x74003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vmsgtu.vx", mask: 0xfc00707f, value: 0x78004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmsgtu.vi", mask: 0xfc00707f, value: 0x78003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vmsgt.vx", mask: 0xfc00707f, value: 0x7c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmsgt.vi", mask: 0xfc00707f, value: 0x7c003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vsaddu.vv", mask: 0xfc00707f, value: 0x80000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vsaddu.vx", mask: 0xfc00707f, value: 0x80004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vsaddu.vi", mask: 0xfc00707f, value: 0x80003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vsadd.vv", mask: 0xfc00707f, value: 0x84000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vsadd.vx", mask: 0xfc00707f, value: 0x84004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vsadd.vi", mask: 0xfc00707f, value: 0x84003057, fields: &["vd", "vs2", "simm5", /* "vm" */] },
+    Encoding { name: "vssubu.vv", mask: 0xfc00707f, value: 0x88000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vssubu.vx", mask: 0xfc00707f, value: 0x88004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vssub.vv", mask: 0xfc00707f, value: 0x8c000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vssub.vx", mask: 0xfc00707f, value: 0x8c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vsll.vv", mask: 0xfc00707f, value: 0x94000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vsll.vx", mask: 0xfc00707f, value: 0x94004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vsll.vi", mask: 0xfc00707f, value: 0x94003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vsmul.vv", mask: 0xfc00707f, value: 0x9c000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vsmul.vx", mask: 0xfc00707f, value: 0x9c004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmv1r.v", mask: 0xfe0ff07f, value: 0x9e003057, fields: &["vd", "vs2"] },
+    Encoding { name: "vmv2r.v", mask: 0xfe0ff07f, value: 0x9e00b057, fields: &["vd", "vs2"] },
+    Encoding { name: "vmv4r.v", mask: 0xfe0ff07f, value: 0x9e01b057, fields: &["vd", "vs2"] },
+    Encoding { name: "vmv8r.v", mask: 0xfe0ff07f, value: 0x9e03b057, fields: &["vd", "vs2"] },
+    Encoding { name: "vsrl.vv", mask: 0xfc00707f, value: 0xa0000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vsrl.vx", mask: 0xfc00707f, value: 0xa0004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vsrl.vi", mask: 0xfc00707f, value: 0xa0003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vsra.vv", mask: 0xfc00707f, value: 0xa4000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vsra.vx", mask: 0xfc00707f, value: 0xa4004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vsra.vi", mask: 0xfc00707f, value: 0xa4003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vssrl.vv", mask: 0xfc00707f, value: 0xa8000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vssrl.vx", mask: 0xfc00707f, value: 0xa8004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vssrl.vi", mask: 0xfc00707f, value: 0xa8003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vssra.vv", mask: 0xfc00707f, value: 0xac000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vssra.vx", mask: 0xfc00707f, value: 0xac004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vssra.vi", mask: 0xfc00707f, value: 0xac003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vnsrl.wv", mask: 0xfc00707f, value: 0xb0000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vnsrl.wx", mask: 0xfc00707f, value: 0xb0004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vnsrl.wi", mask: 0xfc00707f, value: 0xb0003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vnsra.wv", mask: 0xfc00707f, value: 0xb4000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vnsra.wx", mask: 0xfc00707f, value: 0xb4004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vnsra.wi", mask: 0xfc00707f, value: 0xb4003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vnclipu.wv", mask: 0xfc00707f, value: 0xb8000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vnclipu.wx", mask: 0xfc00707f, value: 0xb8004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vnclipu.wi", mask: 0xfc00707f, value: 0xb8003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vnclip.wv", mask: 0xfc00707f, value: 0xbc000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vnclip.wx", mask: 0xfc00707f, value: 0xbc004057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vnclip.wi", mask: 0xfc00707f, value: 0xbc003057, fields: &["vd", "vs2", "uimm5", /* "vm" */] },
+    Encoding { name: "vwredsumu.vs", mask: 0xfc00707f, value: 0xc0000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwredsum.vs", mask: 0xfc00707f, value: 0xc4000057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+
+    Encoding { name: "vredsum.vs", mask: 0xfc00707f, value: 0x00002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vredand.vs", mask: 0xfc00707f, value: 0x04002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vredor.vs", mask: 0xfc00707f, value: 0x08002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vredxor.vs", mask: 0xfc00707f, value: 0x0c002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vredminu.vs", mask: 0xfc00707f, value: 0x10002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vredmin.vs", mask: 0xfc00707f, value: 0x14002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vredmaxu.vs", mask: 0xfc00707f, value: 0x18002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vredmax.vs", mask: 0xfc00707f, value: 0x1c002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vaaddu.vv", mask: 0xfc00707f, value: 0x20002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vaaddu.vx", mask: 0xfc00707f, value: 0x20006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vaadd.vv", mask: 0xfc00707f, value: 0x24002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vaadd.vx", mask: 0xfc00707f, value: 0x24006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vasubu.vv", mask: 0xfc00707f, value: 0x28002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vasubu.vx", mask: 0xfc00707f, value: 0x28006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vasub.vv", mask: 0xfc00707f, value: 0x2c002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vasub.vx", mask: 0xfc00707f, value: 0x2c006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vslide1up.vx", mask: 0xfc00707f, value: 0x38006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vslide1down.vx", mask: 0xfc00707f, value: 0x3c006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmv.x.s", mask: 0xfe0ff07f, value: 0x42002057, fields: &["rd", "vs2"] },
+    Encoding { name: "vcpop.m", mask: 0xfc0ff07f, value: 0x40082057, fields: &["rd", "vs2", /* "vm" */] },
+    Encoding { name: "vfirst.m", mask: 0xfc0ff07f, value: 0x4008a057, fields: &["rd", "vs2", /* "vm" */] },
+    Encoding { name: "vmv.s.x", mask: 0xfff0707f, value: 0x42006057, fields: &["vd", "rs1"] },
+    Encoding { name: "vzext.vf8", mask: 0xfc0ff07f, value: 0x48012057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vsext.vf8", mask: 0xfc0ff07f, value: 0x4801a057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vzext.vf4", mask: 0xfc0ff07f, value: 0x48022057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vsext.vf4", mask: 0xfc0ff07f, value: 0x4802a057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vzext.vf2", mask: 0xfc0ff07f, value: 0x48032057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vsext.vf2", mask: 0xfc0ff07f, value: 0x4803a057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vmsbf.m", mask: 0xfc0ff07f, value: 0x5000a057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vmsof.m", mask: 0xfc0ff07f, value: 0x50012057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vmsif.m", mask: 0xfc0ff07f, value: 0x5001a057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "viota.m", mask: 0xfc0ff07f, value: 0x50082057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vid.v", mask: 0xfdfff07f, value: 0x5008a057, fields: &["vd", /* "vm" */] },
+    Encoding { name: "vcompress.vm", mask: 0xfe00707f, value: 0x5e002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmandn.mm", mask: 0xfe00707f, value: 0x62002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmand.mm", mask: 0xfe00707f, value: 0x66002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmor.mm", mask: 0xfe00707f, value: 0x6a002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmxor.mm", mask: 0xfe00707f, value: 0x6e002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmorn.mm", mask: 0xfe00707f, value: 0x72002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmnand.mm", mask: 0xfe00707f, value: 0x76002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmnor.mm", mask: 0xfe00707f, value: 0x7a002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vmxnor.mm", mask: 0xfe00707f, value: 0x7e002057, fields: &["vd", "vs2", "vs1"] },
+    Encoding { name: "vdivu.vv", mask: 0xfc00707f, value: 0x80002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vdivu.vx", mask: 0xfc00707f, value: 0x80006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vdiv.vv", mask: 0xfc00707f, value: 0x84002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vdiv.vx", mask: 0xfc00707f, value: 0x84006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vremu.vv", mask: 0xfc00707f, value: 0x88002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vremu.vx", mask: 0xfc00707f, value: 0x88006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vrem.vv", mask: 0xfc00707f, value: 0x8c002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vrem.vx", mask: 0xfc00707f, value: 0x8c006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmulhu.vv", mask: 0xfc00707f, value: 0x90002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmulhu.vx", mask: 0xfc00707f, value: 0x90006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmul.vv", mask: 0xfc00707f, value: 0x94002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmul.vx", mask: 0xfc00707f, value: 0x94006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmulhsu.vv", mask: 0xfc00707f, value: 0x98002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmulhsu.vx", mask: 0xfc00707f, value: 0x98006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmulh.vv", mask: 0xfc00707f, value: 0x9c002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmulh.vx", mask: 0xfc00707f, value: 0x9c006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmadd.vv", mask: 0xfc00707f, value: 0xa4002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmadd.vx", mask: 0xfc00707f, value: 0xa4006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vnmsub.vv", mask: 0xfc00707f, value: 0xac002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vnmsub.vx", mask: 0xfc00707f, value: 0xac006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vmacc.vv", mask: 0xfc00707f, value: 0xb4002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmacc.vx", mask: 0xfc00707f, value: 0xb4006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vnmsac.vv", mask: 0xfc00707f, value: 0xbc002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vnmsac.vx", mask: 0xfc00707f, value: 0xbc006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwaddu.vv", mask: 0xfc00707f, value: 0xc0002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwaddu.vx", mask: 0xfc00707f, value: 0xc0006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwadd.vv", mask: 0xfc00707f, value: 0xc4002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwadd.vx", mask: 0xfc00707f, value: 0xc4006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwsubu.vv", mask: 0xfc00707f, value: 0xc8002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwsubu.vx", mask: 0xfc00707f, value: 0xc8006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwsub.vv", mask: 0xfc00707f, value: 0xcc002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwsub.vx", mask: 0xfc00707f, value: 0xcc006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwaddu.wv", mask: 0xfc00707f, value: 0xd0002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwaddu.wx", mask: 0xfc00707f, value: 0xd0006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwadd.wv", mask: 0xfc00707f, value: 0xd4002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwadd.wx", mask: 0xfc00707f, value: 0xd4006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwsubu.wv", mask: 0xfc00707f, value: 0xd8002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwsubu.wx", mask: 0xfc00707f, value: 0xd8006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwsub.wv", mask: 0xfc00707f, value: 0xdc002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwsub.wx", mask: 0xfc00707f, value: 0xdc006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwmulu.vv", mask: 0xfc00707f, value: 0xe0002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwmulu.vx", mask: 0xfc00707f, value: 0xe0006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwmulsu.vv", mask: 0xfc00707f, value: 0xe8002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwmulsu.vx", mask: 0xfc00707f, value: 0xe8006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwmul.vv", mask: 0xfc00707f, value: 0xec002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwmul.vx", mask: 0xfc00707f, value: 0xec006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwmaccu.vv", mask: 0xfc00707f, value: 0xf0002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwmaccu.vx", mask: 0xfc00707f, value: 0xf0006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwmacc.vv", mask: 0xfc00707f, value: 0xf4002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwmacc.vx", mask: 0xfc00707f, value: 0xf4006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwmaccus.vx", mask: 0xfc00707f, value: 0xf8006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+    Encoding { name: "vwmaccsu.vv", mask: 0xfc00707f, value: 0xfc002057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vwmaccsu.vx", mask: 0xfc00707f, value: 0xfc006057, fields: &["vd", "vs2", "rs1", /* "vm" */] },
+
+    Encoding { name: "vfadd.vv", mask: 0xfc00707f, value: 0x00001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfadd.vf", mask: 0xfc00707f, value: 0x00005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfredusum.vs", mask: 0xfc00707f, value: 0x04001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfsub.vv", mask: 0xfc00707f, value: 0x08001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfsub.vf", mask: 0xfc00707f, value: 0x08005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfredosum.vs", mask: 0xfc00707f, value: 0x0c001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmin.vv", mask: 0xfc00707f, value: 0x10001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmin.vf", mask: 0xfc00707f, value: 0x10005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfredmin.vs", mask: 0xfc00707f, value: 0x14001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmax.vv", mask: 0xfc00707f, value: 0x18001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmax.vf", mask: 0xfc00707f, value: 0x18005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfredmax.vs", mask: 0xfc00707f, value: 0x1c001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfsgnj.vv", mask: 0xfc00707f, value: 0x20001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfsgnj.vf", mask: 0xfc00707f, value: 0x20005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfsgnjn.vv", mask: 0xfc00707f, value: 0x24001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfsgnjn.vf", mask: 0xfc00707f, value: 0x24005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfsgnjx.vv", mask: 0xfc00707f, value: 0x28001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfsgnjx.vf", mask: 0xfc00707f, value: 0x28005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfslide1up.vf", mask: 0xfc00707f, value: 0x38005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfslide1down.vf", mask: 0xfc00707f, value: 0x3c005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfmv.f.s", mask: 0xfe0ff07f, value: 0x42001057, fields: &["frd", "vs2"] },
+    Encoding { name: "vfmv.s.f", mask: 0xfff0707f, value: 0x42005057, fields: &["vd", "frs1"] },
+    Encoding { name: "vfcvt.xu.f.v", mask: 0xfc0ff07f, value: 0x48001057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfcvt.x.f.v", mask: 0xfc0ff07f, value: 0x48009057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfcvt.f.xu.v", mask: 0xfc0ff07f, value: 0x48011057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfcvt.f.x.v", mask: 0xfc0ff07f, value: 0x48019057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfcvt.rtz.xu.f.v", mask: 0xfc0ff07f, value: 0x48031057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfcvt.rtz.x.f.v", mask: 0xfc0ff07f, value: 0x48039057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfwcvt.xu.f.v", mask: 0xfc0ff07f, value: 0x48041057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfwcvt.x.f.v", mask: 0xfc0ff07f, value: 0x48049057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfwcvt.f.xu.v", mask: 0xfc0ff07f, value: 0x48051057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfwcvt.f.x.v", mask: 0xfc0ff07f, value: 0x48059057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfwcvt.f.f.v", mask: 0xfc0ff07f, value: 0x48061057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfwcvt.rtz.xu.f.v", mask: 0xfc0ff07f, value: 0x48071057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfwcvt.rtz.x.f.v", mask: 0xfc0ff07f, value: 0x48079057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.xu.f.w", mask: 0xfc0ff07f, value: 0x48081057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.x.f.w", mask: 0xfc0ff07f, value: 0x48089057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.f.xu.w", mask: 0xfc0ff07f, value: 0x48091057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.f.x.w", mask: 0xfc0ff07f, value: 0x48099057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.f.f.w", mask: 0xfc0ff07f, value: 0x480a1057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.rod.f.f.w", mask: 0xfc0ff07f, value: 0x480a9057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.rtz.xu.f.w", mask: 0xfc0ff07f, value: 0x480b1057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfncvt.rtz.x.f.w", mask: 0xfc0ff07f, value: 0x480b9057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfsqrt.v", mask: 0xfc0ff07f, value: 0x4c001057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfrsqrt7.v", mask: 0xfc0ff07f, value: 0x4c021057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfrec7.v", mask: 0xfc0ff07f, value: 0x4c029057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfclass.v", mask: 0xfc0ff07f, value: 0x4c081057, fields: &["vd", "vs2", /* "vm" */] },
+    Encoding { name: "vfmerge.vfm", mask: 0xfe00707f, value: 0x5c005057, fields: &["vd", "vs2", "frs1"] },
+    Encoding { name: "vfmv.v.f", mask: 0xfff0707f, value: 0x5e005057, fields: &["vd", "frs1"] },
+    Encoding { name: "vmfeq.vv", mask: 0xfc00707f, value: 0x60001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmfeq.vf", mask: 0xfc00707f, value: 0x60005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vmfle.vv", mask: 0xfc00707f, value: 0x64001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmfle.vf", mask: 0xfc00707f, value: 0x64005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vmflt.vv", mask: 0xfc00707f, value: 0x6c001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmflt.vf", mask: 0xfc00707f, value: 0x6c005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vmfne.vv", mask: 0xfc00707f, value: 0x70001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vmfne.vf", mask: 0xfc00707f, value: 0x70005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vmfgt.vf", mask: 0xfc00707f, value: 0x74005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vmfge.vf", mask: 0xfc00707f, value: 0x7c005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfdiv.vv", mask: 0xfc00707f, value: 0x80001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfdiv.vf", mask: 0xfc00707f, value: 0x80005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfrdiv.vf", mask: 0xfc00707f, value: 0x84005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfmul.vv", mask: 0xfc00707f, value: 0x90001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmul.vf", mask: 0xfc00707f, value: 0x90005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfrsub.vf", mask: 0xfc00707f, value: 0x9c005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfmadd.vv", mask: 0xfc00707f, value: 0xa0001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmadd.vf", mask: 0xfc00707f, value: 0xa0005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfnmadd.vv", mask: 0xfc00707f, value: 0xa4001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfnmadd.vf", mask: 0xfc00707f, value: 0xa4005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfmsub.vv", mask: 0xfc00707f, value: 0xa8001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmsub.vf", mask: 0xfc00707f, value: 0xa8005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfnmsub.vv", mask: 0xfc00707f, value: 0xac001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfnmsub.vf", mask: 0xfc00707f, value: 0xac005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfmacc.vv", mask: 0xfc00707f, value: 0xb0001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmacc.vf", mask: 0xfc00707f, value: 0xb0005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfnmacc.vv", mask: 0xfc00707f, value: 0xb4001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfnmacc.vf", mask: 0xfc00707f, value: 0xb4005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfmsac.vv", mask: 0xfc00707f, value: 0xb8001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfmsac.vf", mask: 0xfc00707f, value: 0xb8005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfnmsac.vv", mask: 0xfc00707f, value: 0xbc001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfnmsac.vf", mask: 0xfc00707f, value: 0xbc005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwadd.vv", mask: 0xfc00707f, value: 0xc0001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwadd.vf", mask: 0xfc00707f, value: 0xc0005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwredusum.vs", mask: 0xfc00707f, value: 0xc4001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwsub.vv", mask: 0xfc00707f, value: 0xc8001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwsub.vf", mask: 0xfc00707f, value: 0xc8005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwredosum.vs", mask: 0xfc00707f, value: 0xcc001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwadd.wv", mask: 0xfc00707f, value: 0xd0001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwadd.wf", mask: 0xfc00707f, value: 0xd0005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwsub.wv", mask: 0xfc00707f, value: 0xd8001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwsub.wf", mask: 0xfc00707f, value: 0xd8005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwmul.vv", mask: 0xfc00707f, value: 0xe0001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwmul.vf", mask: 0xfc00707f, value: 0xe0005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwmacc.vv", mask: 0xfc00707f, value: 0xf0001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwmacc.vf", mask: 0xfc00707f, value: 0xf0005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwnmacc.vv", mask: 0xfc00707f, value: 0xf4001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwnmacc.vf", mask: 0xfc00707f, value: 0xf4005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwmsac.vv", mask: 0xfc00707f, value: 0xf8001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwmsac.vf", mask: 0xfc00707f, value: 0xf8005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+    Encoding { name: "vfwnmsac.vv", mask: 0xfc00707f, value: 0xfc001057, fields: &["vd", "vs2", "vs1", /* "vm" */] },
+    Encoding { name: "vfwnmsac.vf", mask: 0xfc00707f, value: 0xfc005057, fields: &["vd", "vs2", "frs1", /* "vm" */] },
+
+    // rv_zba
+    Encoding { name: "sh1add", mask: 0xfe00707f, value: 0x20002033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "sh2add", mask: 0xfe00707f, value: 0x20004033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "sh3add", mask: 0xfe00707f, value: 0x20006033, fields: &["rd", "rs1", "rs2"] },
+
+    // rv_zbb
+    Encoding { name: "andn", mask: 0xfe00707f, value: 0x40007033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "orn", mask: 0xfe00707f, value: 0x40006033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "xnor", mask: 0xfe00707f, value: 0x40004033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "max", mask: 0xfe00707f, value: 0x0a006033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "maxu", mask: 0xfe00707f, value: 0x0a007033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "min", mask: 0xfe00707f, value: 0x0a004033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "minu", mask: 0xfe00707f, value: 0x0a005033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "rol", mask: 0xfe00707f, value: 0x60001033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "ror", mask: 0xfe00707f, value: 0x60005033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "clz", mask: 0xfff0707f, value: 0x60001013, fields: &["rd", "rs1"] },
+    Encoding { name: "ctz", mask: 0xfff0707f, value: 0x60101013, fields: &["rd", "rs1"] },
+    Encoding { name: "cpop", mask: 0xfff0707f, value: 0x60201013, fields: &["rd", "rs1"] },
+    Encoding { name: "sext.b", mask: 0xfff0707f, value: 0x60401013, fields: &["rd", "rs1"] },
+    Encoding { name: "sext.h", mask: 0xfff0707f, value: 0x60501013, fields: &["rd", "rs1"] },
+    Encoding { name: "orc.b", mask: 0xfff0707f, value: 0x28705013, fields: &["rd", "rs1"] },
+
+    // rv_zbc
+    Encoding { name: "clmul", mask: 0xfe00707f, value: 0x0a001033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "clmulr", mask: 0xfe00707f, value: 0x0a002033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "clmulh", mask: 0xfe00707f, value: 0x0a003033, fields: &["rd", "rs1", "rs2"] },
+
+    // rv_zbs
+    Encoding { name: "bclr", mask: 0xfe00707f, value: 0x48001033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "bext", mask: 0xfe00707f, value: 0x48005033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "binv", mask: 0xfe00707f, value: 0x68001033, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "bset", mask: 0xfe00707f, value: 0x28001033, fields: &["rd", "rs1", "rs2"] },
+];
+
+#[rustfmt::skip]
+static ENCODINGS_32_RV32_DATA: &[Encoding] = &[
+    // rv32_i
+    Encoding { name: "slli", mask: 0xfe00707f, value: 0x00001013, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "srli", mask: 0xfe00707f, value: 0x00005013, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "srai", mask: 0xfe00707f, value: 0x40005013, fields: &["rd", "rs1", "shamtw"] },
+
+    // rv32_zbb
+    Encoding { name: "rori", mask: 0xfe00707f, value: 0x60005013, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "rev8", mask: 0xfff0707f, value: 0x69805013, fields: &["rd", "rs1"] },
+    Encoding { name: "zext.h", mask: 0xfff0707f, value: 0x08004033, fields: &["rd", "rs1"] },
+
+    // rv32_zbs
+    Encoding { name: "bclri", mask: 0xfe00707f, value: 0x48001013, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "bexti", mask: 0xfe00707f, value: 0x48005013, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "binvi", mask: 0xfe00707f, value: 0x68001013, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "bseti", mask: 0xfe00707f, value: 0x28001013, fields: &["rd", "rs1", "shamtw"] },
+];
+
+#[rustfmt::skip]
+static ENCODINGS_32_RV64_DATA: &[Encoding] = &[
+    // rv64_i
+    Encoding { name: "lwu", mask: 0x0000707f, value: 0x00006003, fields: &["rd", "rs1", "imm12"] },
+    Encoding { name: "ld", mask: 0x0000707f, value: 0x00003003, fields: &["rd", "rs1", "imm12"] },
+    Encoding { name: "sd", mask: 0x0000707f, value: 0x00003023, fields: &["rs1", "rs2", "imm12hilo"] },
+    Encoding { name: "slli", mask: 0xfc00707f, value: 0x00001013, fields: &["rd", "rs1", "shamtd"] },
+    Encoding { name: "srli", mask: 0xfc00707f, value: 0x00005013, fields: &["rd", "rs1", "shamtd"] },
+    Encoding { name: "srai", mask: 0xfc00707f, value: 0x40005013, fields: &["rd", "rs1", "shamtd"] },
+    Encoding { name: "addiw", mask: 0x0000707f, value: 0x0000001b, fields: &["rd", "rs1", "imm12"] },
+    Encoding { name: "slliw", mask: 0xfe00707f, value: 0x0000101b, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "srliw", mask: 0xfe00707f, value: 0x0000501b, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "sraiw", mask: 0xfe00707f, value: 0x4000501b, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "addw", mask: 0xfe00707f, value: 0x0000003b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "subw", mask: 0xfe00707f, value: 0x4000003b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "sllw", mask: 0xfe00707f, value: 0x0000103b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "srlw", mask: 0xfe00707f, value: 0x0000503b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "sraw", mask: 0xfe00707f, value: 0x4000503b, fields: &["rd", "rs1", "rs2"] },
+
+    // rv64_m
+    Encoding { name: "mulw", mask: 0xfe00707f, value: 0x0200003b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "divw", mask: 0xfe00707f, value: 0x0200403b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "divuw", mask: 0xfe00707f, value: 0x0200503b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "remw", mask: 0xfe00707f, value: 0x0200603b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "remuw", mask: 0xfe00707f, value: 0x0200703b, fields: &["rd", "rs1", "rs2"] },
+
+    // rv64_a
+    Encoding { name: "lr.d", mask: 0xf9f0707f, value: 0x1000302f, fields: &["rd", "rs1", /* "aq", "rl" */] },
+    Encoding { name: "sc.d", mask: 0xf800707f, value: 0x1800302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amoswap.d", mask: 0xf800707f, value: 0x0800302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amoadd.d", mask: 0xf800707f, value: 0x0000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amoxor.d", mask: 0xf800707f, value: 0x2000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amoand.d", mask: 0xf800707f, value: 0x6000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amoor.d", mask: 0xf800707f, value: 0x4000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amomin.d", mask: 0xf800707f, value: 0x8000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amomax.d", mask: 0xf800707f, value: 0xa000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amominu.d", mask: 0xf800707f, value: 0xc000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+    Encoding { name: "amomaxu.d", mask: 0xf800707f, value: 0xe000302f, fields: &["rd", "rs1", "rs2", /* "aq", "rl" */] },
+
+    // rv64_f
+    Encoding { name: "fcvt.l.s", mask: 0xfff0007f, value: 0xc0200053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.lu.s", mask: 0xfff0007f, value: 0xc0300053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.s.l", mask: 0xfff0007f, value: 0xd0200053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fcvt.s.lu", mask: 0xfff0007f, value: 0xd0300053, fields: &["frd", "rs1", "rm"] },
+
+    // rv64_d
+    Encoding { name: "fcvt.l.d", mask: 0xfff0007f, value: 0xc2200053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.lu.d", mask: 0xfff0007f, value: 0xc2300053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.d.l", mask: 0xfff0007f, value: 0xd2200053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fcvt.d.lu", mask: 0xfff0007f, value: 0xd2300053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fmv.x.d", mask: 0xfff0707f, value: 0xe2000053, fields: &["rd", "frs1"] },
+    Encoding { name: "fmv.d.x", mask: 0xfff0707f, value: 0xf2000053, fields: &["frd", "rs1"] },
+
+    // rv64_q
+    Encoding { name: "fcvt.l.q", mask: 0xfff0007f, value: 0xc6200053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.lu.q", mask: 0xfff0007f, value: 0xc6300053, fields: &["rd", "frs1", "rm"] },
+    Encoding { name: "fcvt.q.l", mask: 0xfff0007f, value: 0xd6200053, fields: &["frd", "rs1", "rm"] },
+    Encoding { name: "fcvt.q.lu", mask: 0xfff0007f, value: 0xd6300053, fields: &["frd", "rs1", "rm"] },
+
+    // rv64_zba
+    Encoding { name: "add.uw", mask: 0xfe00707f, value: 0x0800003b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "sh1add.uw", mask: 0xfe00707f, value: 0x2000203b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "sh2add.uw", mask: 0xfe00707f, value: 0x2000403b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "sh3add.uw", mask: 0xfe00707f, value: 0x2000603b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "slli.uw", mask: 0xfc00707f, value: 0x0800101b, fields: &["rd", "rs1", "shamtd"] },
+
+    // rv64_zbb
+    Encoding { name: "clzw", mask: 0xfff0707f, value: 0x6000101b, fields: &["rd", "rs1"] },
+    Encoding { name: "ctzw", mask: 0xfff0707f, value: 0x6010101b, fields: &["rd", "rs1"] },
+    Encoding { name: "cpopw", mask: 0xfff0707f, value: 0x6020101b, fields: &["rd", "rs1"] },
+    Encoding { name: "rolw", mask: 0xfe00707f, value: 0x6000103b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "rorw", mask: 0xfe00707f, value: 0x6000503b, fields: &["rd", "rs1", "rs2"] },
+    Encoding { name: "rori", mask: 0xfc00707f, value: 0x60005013, fields: &["rd", "rs1", "shamtd"] },
+    Encoding { name: "roriw", mask: 0xfe00707f, value: 0x6000501b, fields: &["rd", "rs1", "shamtw"] },
+    Encoding { name: "rev8", mask: 0xfff0707f, value: 0x6b805013, fields: &["rd", "rs1"] },
+    Encoding { name: "zext.h", mask: 0xfff0707f, value: 0x0800403b, fields: &["rd", "rs1"] },
+
+    // rv64_zbs
+    Encoding { name: "bclri", mask: 0xfc00707f, value: 0x48001013, fields: &["rd", "rs1", "shamtd"] },
+    Encoding { name: "bexti", mask: 0xfc00707f, value: 0x48005013, fields: &["rd", "rs1", "shamtd"] },
+    Encoding { name: "binvi", mask: 0xfc00707f, value: 0x68001013, fields: &["rd", "rs1", "shamtd"] },
+    Encoding { name: "bseti", mask: 0xfc00707f, value: 0x28001013, fields: &["rd", "rs1", "shamtd"] },
 ];
 
 #[rustfmt::skip]
@@ -178,18 +1082,70 @@ static ENCODINGS_16_DATA: &[Encoding] = &[
     Encoding { name: "c.jalr", mask: 0xf07f, value: 0x9002, fields: &["c_rs1_n0"] },
     Encoding { name: "c.add", mask: 0xf003, value: 0x9002, fields: &["rd_rs1", "c_rs2_n0"] },
     Encoding { name: "c.swsp", mask: 0xe003, value: 0xc002, fields: &["c_rs2", "c_uimm8sp_s"] },
+    Encoding { name: "c.srli", mask: 0xec03, value: 0x8001, fields: &["rd_rs1_p", "c_nzuimm6hilo"] },
+    Encoding { name: "c.srai", mask: 0xec03, value: 0x8401, fields: &["rd_rs1_p", "c_nzuimm6hilo"] },
+    Encoding { name: "c.slli", mask: 0xe003, value: 0x0002, fields: &["rd_rs1_n0", "c_nzuimm6hilo"] },
+
+    // rv_zcb
+    Encoding { name: "c.lbu", mask: 0xfc03, value: 0x8000, fields: &["rd_p", "rs1_p", "c_uimm2"] },
+    Encoding { name: "c.lhu", mask: 0xfc43, value: 0x8400, fields: &["rd_p", "rs1_p", "c_uimm1"] },
+    Encoding { name: "c.lh", mask: 0xfc43, value: 0x8440, fields: &["rd_p", "rs1_p", "c_uimm1"] },
+    Encoding { name: "c.sb", mask: 0xfc03, value: 0x8800, fields: &["rs1_p", "rs2_p", "c_uimm2"] },
+    Encoding { name: "c.sh", mask: 0xfc43, value: 0x8c00, fields: &["rs1_p", "rs2_p", "c_uimm1"] },
+    Encoding { name: "c.zext.b", mask: 0xfc7f, value: 0x9c61, fields: &["rd_rs1_p"] },
+    Encoding { name: "c.sext.b", mask: 0xfc7f, value: 0x9c65, fields: &["rd_rs1_p"] },
+    Encoding { name: "c.zext.h", mask: 0xfc7f, value: 0x9c69, fields: &["rd_rs1_p"] },
+    Encoding { name: "c.sext.h", mask: 0xfc7f, value: 0x9c6d, fields: &["rd_rs1_p"] },
+    Encoding { name: "c.not", mask: 0xfc7f, value: 0x9c75, fields: &["rd_rs1_p"] },
+    Encoding { name: "c.mul", mask: 0xfc63, value: 0x9c41, fields: &["rd_rs1_p", "rs2_p"] },
+];
+
+/// Encodings that Zcmp reuses, see `Extensions`
+#[rustfmt::skip]
+static ENCODINGS_16_ZCD_DATA: &[Encoding] = &[
+    // rv_c_d
+    Encoding { name: "c.fld", mask: 0xe003, value: 0x2000, fields: &["frd_p", "rs1_p", "c_uimm8hilo"] },
+    Encoding { name: "c.fsd", mask: 0xe003, value: 0xa000, fields: &["rs1_p", "frs2_p", "c_uimm8hilo"] },
+    Encoding { name: "c.fldsp", mask: 0xe003, value: 0x2002, fields: &["frd", "c_uimm9sphilo"] },
+    Encoding { name: "c.fsdsp", mask: 0xe003, value: 0xa002, fields: &["c_frs2", "c_uimm9sp_s"] },
+];
+
+#[rustfmt::skip]
+static ENCODINGS_16_ZCMP_DATA: &[Encoding] = &[
+    // rv_zcmp
+    Encoding { name: "cm.push", mask: 0xff03, value: 0xb802, fields: &["c_rlist", "c_spimm"] },
+    Encoding { name: "cm.pop", mask: 0xff03, value: 0xba02, fields: &["c_rlist", "c_spimm"] },
+    Encoding { name: "cm.popretz", mask: 0xff03, value: 0xbc02, fields: &["c_rlist", "c_spimm"] },
+    Encoding { name: "cm.popret", mask: 0xff03, value: 0xbe02, fields: &["c_rlist", "c_spimm"] },
+    Encoding { name: "cm.mvsa01", mask: 0xfc63, value: 0xac22, fields: &["c_sreg1", "c_sreg2"] },
+    Encoding { name: "cm.mva01s", mask: 0xfc63, value: 0xac62, fields: &["c_sreg1", "c_sreg2"] },
+];
+
+#[rustfmt::skip]
+static ENCODINGS_16_RV32_DATA: &[Encoding] = &[
+    // rv32_c
+    Encoding { name: "c.jal", mask: 0xe003, value: 0x2001, fields: &["c_imm12"] },
 
+    // rv32_c_f
+    Encoding { name: "c.flw", mask: 0xe003, value: 0x6000, fields: &["frd_p", "rs1_p", "c_uimm7hilo"] },
+    Encoding { name: "c.fsw", mask: 0xe003, value: 0xe000, fields: &["rs1_p", "frs2_p", "c_uimm7hilo"] },
+    Encoding { name: "c.flwsp", mask: 0xe003, value: 0x6002, fields: &["frd", "c_uimm8sphilo"] },
+    Encoding { name: "c.fswsp", mask: 0xe003, value: 0xe002, fields: &["c_frs2", "c_uimm8sp_s"] },
+];
+
+#[rustfmt::skip]
+static ENCODINGS_16_RV64_DATA: &[Encoding] = &[
     // rv64_c
     Encoding { name: "c.ld", mask: 0xe003, value: 0x6000, fields: &["rd_p", "rs1_p", "c_uimm8hilo"] },
     Encoding { name: "c.sd", mask: 0xe003, value: 0xe000, fields: &["rs1_p", "rs2_p", "c_uimm8hilo"] },
     Encoding { name: "c.addiw", mask: 0xe003, value: 0x2001, fields: &["rd_rs1", "c_imm6hilo"] },
-    Encoding { name: "c.srli", mask: 0xec03, value: 0x8001, fields: &["rd_rs1_p", "c_nzuimm6hilo"] },
-    Encoding { name: "c.srai", mask: 0xec03, value: 0x8401, fields: &["rd_rs1_p", "c_nzuimm6hilo"] },
     Encoding { name: "c.subw", mask: 0xfc63, value: 0x9c01, fields: &["rd_rs1_p", "rs2_p"] },
     Encoding { name: "c.addw", mask: 0xfc63, value: 0x9c21, fields: &["rd_rs1_p", "rs2_p"] },
-    Encoding { name: "c.slli", mask: 0xe003, value: 0x0002, fields: &["rd_rs1_n0", "c_nzuimm6hilo"] },
     Encoding { name: "c.ldsp", mask: 0xe003, value: 0x6002, fields: &["rd_n0", "c_uimm9sphilo"] },
     Encoding { name: "c.sdsp", mask: 0xe003, value: 0xe002, fields: &["c_rs2", "c_uimm9sp_s"] },
+
+    // rv64_zcb
+    Encoding { name: "c.zext.w", mask: 0xfc7f, value: 0x9c71, fields: &["rd_rs1_p"] },
 ];
 
 fn format_value(value: i64, f: &mut fmt::Formatter) -> fmt::Result {
@@ -200,6 +1156,51 @@ fn format_reg(value: i64, f: &mut fmt::Formatter) -> fmt::Result {
     write!(f, "{}", REG_NAMES[value as usize])
 }
 
+fn format_freg(value: i64, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", FREG_NAMES[value as usize])
+}
+
+fn format_rm(value: i64, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", RM_NAMES[value as usize])
+}
+
+/// Registers saved by Zcmp for each value of the rlist field, which is 4 to 15
+static RLIST_NAMES: &[&str] = &[
+    "{ra}",
+    "{ra, s0}",
+    "{ra, s0-s1}",
+    "{ra, s0-s2}",
+    "{ra, s0-s3}",
+    "{ra, s0-s4}",
+    "{ra, s0-s5}",
+    "{ra, s0-s6}",
+    "{ra, s0-s7}",
+    "{ra, s0-s8}",
+    "{ra, s0-s9}",
+    "{ra, s0-s11}",
+];
+
+fn format_rlist(value: i64, f: &mut fmt::Formatter) -> fmt::Result {
+    match RLIST_NAMES.get((value as usize).wrapping_sub(4)) {
+        Some(name) => write!(f, "{name}"),
+        None => write!(f, "{value}"),
+    }
+}
+
+fn format_vreg(value: i64, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", VREG_NAMES[value as usize])
+}
+
+/// Whether a field holds a floating-point register
+pub fn is_freg_field(name: &str) -> bool {
+    name.starts_with("fr") || name.starts_with("c_fr")
+}
+
+/// Whether a field holds a vector register
+pub fn is_vreg_field(name: &str) -> bool {
+    matches!(name, "vd" | "vs1" | "vs2" | "vs3")
+}
+
 fn uf(v: u32, s: u32, l: u32) -> i64 {
     ((v >> s) & ((1 << l) - 1)) as i64
 }
@@ -208,6 +1209,15 @@ fn sf(v: u32, s: u32, l: u32) -> i64 {
     (v as i64) << (64 - s - l) >> (64 - l)
 }
 
+/// Register number of the 3-bit register fields of Zcmp, s0 to s7
+fn sreg(v: i64) -> i64 {
+    if v < 2 {
+        8 + v
+    } else {
+        16 + v
+    }
+}
+
 #[rustfmt::skip]
 static FIELDS_DATA: &[(&str, Field)] = &[
     ("rd", Field { format: format_reg, decode: |v| uf(v,7,5) }),
@@ -222,6 +1232,21 @@ static FIELDS_DATA: &[(&str, Field)] = &[
     ("shamtd", Field { format: format_value, decode: |v| uf(v,20,6) }),
     ("shamtw", Field { format: format_value, decode: |v| uf(v,20,5) }),
 
+    ("frd", Field { format: format_freg, decode: |v| uf(v,7,5) }),
+    ("frs1", Field { format: format_freg, decode: |v| uf(v,15,5) }),
+    ("frs2", Field { format: format_freg, decode: |v| uf(v,20,5) }),
+    ("frs3", Field { format: format_freg, decode: |v| uf(v,27,5) }),
+    ("rm", Field { format: format_rm, decode: |v| uf(v,12,3) }),
+
+    ("vd", Field { format: format_vreg, decode: |v| uf(v,7,5) }),
+    ("vs1", Field { format: format_vreg, decode: |v| uf(v,15,5) }),
+    ("vs2", Field { format: format_vreg, decode: |v| uf(v,20,5) }),
+    ("vs3", Field { format: format_vreg, decode: |v| uf(v,7,5) }),
+    ("simm5", Field { format: format_value, decode: |v| sf(v,15,5) }),
+    ("uimm5", Field { format: format_value, decode: |v| uf(v,15,5) }),
+    ("zimm10", Field { format: format_value, decode: |v| uf(v,20,10) }),
+    ("zimm11", Field { format: format_value, decode: |v| uf(v,20,11) }),
+
     // ("fm", Field { format: format_value, decode: |v| todo!() }),
     // ("pred", Field { format: format_value, decode: |v| todo!() }),
     // ("succ", Field { format: format_value, decode: |v| todo!() }),
@@ -244,6 +1269,18 @@ static FIELDS_DATA: &[(&str, Field)] = &[
     ("c_rs2", Field { format: format_reg, decode: |v| uf(v,2,5) }),
     ("c_rs2_n0", Field { format: format_reg, decode: |v| uf(v,2,5) }),
 
+    ("frd_p", Field { format: format_freg, decode: |v| 8 + uf(v,2,3) }),
+    ("frs2_p", Field { format: format_freg, decode: |v| 8 + uf(v,2,3) }),
+    ("c_frs2", Field { format: format_freg, decode: |v| uf(v,2,5) }),
+
+    ("c_uimm1", Field { format: format_value, decode: |v| uf(v,5,1) << 1 }),
+    ("c_uimm2", Field { format: format_value, decode: |v| uf(v,6,1) | (uf(v,5,1) << 1) }),
+
+    ("c_rlist", Field { format: format_rlist, decode: |v| uf(v,4,4) }),
+    ("c_spimm", Field { format: format_value, decode: |v| uf(v,2,2) << 4 }),
+    ("c_sreg1", Field { format: format_reg, decode: |v| sreg(uf(v,7,3)) }),
+    ("c_sreg2", Field { format: format_reg, decode: |v| sreg(uf(v,2,3)) }),
+
     ("c_bimm9hilo", Field { format: format_value, decode: |v| (uf(v,3,2) << 1) + (uf(v,10,2) << 3) + (uf(v,2,1) << 5) + (uf(v,5,2) << 6) + (sf(v,12, 1) << 8) }),
     ("c_imm12", Field { format: format_value, decode: |v| (uf(v,3,3) << 1) + (uf(v,11,1) << 4) + (uf(v,2,1) << 5) + (uf(v,7,1) << 6) + (uf(v,6,1) << 7) + (uf(v,9,2) << 8) + (uf(v,8,1) << 10) + (sf(v,12,1) << 11) }),
     ("c_imm6hilo", Field { format: format_value, decode: |v| uf(v,2,5) + (sf(v,12,1) << 5) }),
@@ -260,42 +1297,80 @@ static FIELDS_DATA: &[(&str, Field)] = &[
     ("c_uimm9sphilo", Field { format: format_value, decode: |v| (uf(v,5,2) << 3) + (uf(v,12,1) << 5) + (uf(v,2,3) << 6) }),
 ];
 
-lazy_static! {
-    static ref ENCODINGS_32: HashMap<u32, Vec<Encoding>> = {
-        let mut res: HashMap<u32, Vec<Encoding>> = HashMap::new();
+fn encodings_32(tables: &[&[Encoding]]) -> HashMap<u32, Vec<Encoding>> {
+    let mut res: HashMap<u32, Vec<Encoding>> = HashMap::new();
 
-        for enc in ENCODINGS_32_DATA {
-            assert!(enc.mask & 0x7f == 0x7f);
-            res.entry(enc.value & 0x7f).or_default().push(*enc);
-        }
+    for enc in tables.iter().copied().flatten() {
+        assert!(enc.mask & 0x7f == 0x7f);
+        res.entry(enc.value & 0x7f).or_default().push(*enc);
+    }
+
+    res
+}
 
-        res
-    };
-    static ref ENCODINGS_16: HashMap<u32, Vec<Encoding>> = {
-        let mut res: HashMap<u32, Vec<Encoding>> = HashMap::new();
+fn encodings_16(tables: &[&[Encoding]]) -> HashMap<u32, Vec<Encoding>> {
+    let mut res: HashMap<u32, Vec<Encoding>> = HashMap::new();
 
-        for enc in ENCODINGS_16_DATA {
-            assert!((enc.mask >> 13 << 2) | (enc.mask & 0x3) == 0b11111);
-            res.entry((enc.value >> 13 << 2) | (enc.value & 0x3))
-                .or_default()
-                .push(*enc);
-        }
+    for enc in tables.iter().copied().flatten() {
+        assert!((enc.mask >> 13 << 2) | (enc.mask & 0x3) == 0b11111);
+        res.entry((enc.value >> 13 << 2) | (enc.value & 0x3))
+            .or_default()
+            .push(*enc);
+    }
 
-        res
-    };
+    res
+}
+
+lazy_static! {
+    static ref ENCODINGS_32_RV32: HashMap<u32, Vec<Encoding>> =
+        encodings_32(&[ENCODINGS_32_DATA, ENCODINGS_32_RV32_DATA]);
+    static ref ENCODINGS_32_RV64: HashMap<u32, Vec<Encoding>> =
+        encodings_32(&[ENCODINGS_32_DATA, ENCODINGS_32_RV64_DATA]);
+    static ref ENCODINGS_16_RV32: HashMap<u32, Vec<Encoding>> = encodings_16(&[
+        ENCODINGS_16_DATA,
+        ENCODINGS_16_ZCD_DATA,
+        ENCODINGS_16_RV32_DATA
+    ]);
+    static ref ENCODINGS_16_RV64: HashMap<u32, Vec<Encoding>> = encodings_16(&[
+        ENCODINGS_16_DATA,
+        ENCODINGS_16_ZCD_DATA,
+        ENCODINGS_16_RV64_DATA
+    ]);
+    static ref ENCODINGS_16_RV32_ZCMP: HashMap<u32, Vec<Encoding>> = encodings_16(&[
+        ENCODINGS_16_DATA,
+        ENCODINGS_16_ZCMP_DATA,
+        ENCODINGS_16_RV32_DATA
+    ]);
+    static ref ENCODINGS_16_RV64_ZCMP: HashMap<u32, Vec<Encoding>> = encodings_16(&[
+        ENCODINGS_16_DATA,
+        ENCODINGS_16_ZCMP_DATA,
+        ENCODINGS_16_RV64_DATA
+    ]);
     static ref FIELDS: HashMap<&'static str, Field> = FIELDS_DATA.iter().copied().collect();
 }
 
-pub fn decode(insn: u32) -> Option<Encoding> {
+pub fn decode(insn: u32, xlen: Xlen, ext: Extensions) -> Option<Encoding> {
     if insn & 0b11 == 0b11 {
         // 32b insn
-        ENCODINGS_32.get(&(insn & 0x7f)).and_then(|encs| {
+        let encodings = match xlen {
+            Xlen::Rv32 => &*ENCODINGS_32_RV32,
+            Xlen::Rv64 => &*ENCODINGS_32_RV64,
+        };
+
+        encodings.get(&(insn & 0x7f)).and_then(|encs| {
             encs.iter()
                 .find(|enc| (insn & enc.mask) == enc.value)
                 .copied()
         })
     } else {
-        ENCODINGS_16
+        let encodings = match (xlen, ext.zcmp) {
+            (Xlen::Rv32, false) => &*ENCODINGS_16_RV32,
+            (Xlen::Rv64, false) => &*ENCODINGS_16_RV64,
+            (Xlen::Rv32, true) => &*ENCODINGS_16_RV32_ZCMP,
+            (Xlen::Rv64, true) => &*ENCODINGS_16_RV64_ZCMP,
+        };
+
+        encodings
             .get(&((insn >> 13 << 2) | (insn & 0x3)))
             .and_then(|encs| {
                 encs.iter()
@@ -345,9 +1420,9 @@ impl fmt::Display for Disassembly {
     }
 }
 
-pub fn disassemble(insn: u32) -> Disassembly {
+pub fn disassemble(insn: u32, xlen: Xlen, ext: Extensions) -> Disassembly {
     Disassembly {
         insn,
-        encoding: decode(insn),
+        encoding: decode(insn, xlen, ext),
     }
 }
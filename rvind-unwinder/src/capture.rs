@@ -0,0 +1,100 @@
+//! Stack capture for contexts where even a callback is too much, such as
+//! profiling interrupts.
+
+use crate::{FirstFrame, MemoryReader, UnwindTable, Unwinder};
+
+impl<T: UnwindTable + ?Sized, M: MemoryReader> Unwinder<'_, T, M> {
+    /// Stores the pc of each remaining frame into `buf`, and returns how many
+    /// were stored.
+    ///
+    /// Stops when `buf` is full, after the outermost frame, or at the first
+    /// frame that cannot be unwound. Takes at most `buf.len()` steps.
+    pub fn capture(&mut self, buf: &mut [usize]) -> usize {
+        let mut len = 0;
+
+        for slot in buf {
+            match self.next() {
+                Some(Ok(frame)) => *slot = frame.pc,
+                _ => break,
+            }
+
+            len += 1;
+        }
+
+        len
+    }
+}
+
+/// Stores the pc of each frame starting from `first_frame` into `buf`, and
+/// returns how many were stored.
+///
+/// `buf[0]` is the pc of `first_frame`, and the rest are return addresses.
+/// Nothing is allocated and no frame takes more than one table lookup, so this
+/// can be called from an interrupt handler as long as `table` and `memory`
+/// can.
+pub fn capture<T: UnwindTable + ?Sized, M: MemoryReader>(
+    table: &T,
+    first_frame: FirstFrame,
+    memory: M,
+    buf: &mut [usize],
+) -> usize {
+    Unwinder::new(table, first_frame, memory).capture(buf)
+}
+
+#[cfg(target_arch = "riscv64")]
+impl FirstFrame {
+    /// The registers of the calling function at the point of the call.
+    ///
+    /// Always inlined, so that the pc is in the caller and described by the
+    /// caller's unwind entries.
+    #[inline(always)]
+    pub fn current() -> Self {
+        let mut regs = [0usize; 32];
+
+        // SAFETY: Only stores to `regs`. The pointer is kept in a temporary
+        // register so that it cannot take the place of a register being saved,
+        // and t0 and t1 hold nothing of the caller's by then.
+        unsafe {
+            core::arch::asm!(
+                "sd ra, 8(t0)",
+                "sd sp, 16(t0)",
+                "sd gp, 24(t0)",
+                "sd tp, 32(t0)",
+                "sd t0, 40(t0)",
+                "sd t1, 48(t0)",
+                "sd t2, 56(t0)",
+                "sd s0, 64(t0)",
+                "sd s1, 72(t0)",
+                "sd a0, 80(t0)",
+                "sd a1, 88(t0)",
+                "sd a2, 96(t0)",
+                "sd a3, 104(t0)",
+                "sd a4, 112(t0)",
+                "sd a5, 120(t0)",
+                "sd a6, 128(t0)",
+                "sd a7, 136(t0)",
+                "sd s2, 144(t0)",
+                "sd s3, 152(t0)",
+                "sd s4, 160(t0)",
+                "sd s5, 168(t0)",
+                "sd s6, 176(t0)",
+                "sd s7, 184(t0)",
+                "sd s8, 192(t0)",
+                "sd s9, 200(t0)",
+                "sd s10, 208(t0)",
+                "sd s11, 216(t0)",
+                "sd t3, 224(t0)",
+                "sd t4, 232(t0)",
+                "sd t5, 240(t0)",
+                "sd t6, 248(t0)",
+                "auipc t1, 0",
+                "sd t1, 0(t0)",
+                in("t0") regs.as_mut_ptr(),
+                out("t1") _,
+                options(nostack, preserves_flags),
+            );
+        }
+
+        Self::from_regs(regs)
+    }
+}
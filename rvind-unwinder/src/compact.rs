@@ -0,0 +1,246 @@
+//! Compact unwind table format.
+//!
+//! The table starts with a [`CompactHeader`], followed by the rule pool, the
+//! block index and the entry stream:
+//!
+//! - The rule pool is an array of [`Rule`], each distinct rule stored once.
+//! - The block index is an array of [`Block`], sorted by code offset.
+//! - The entry stream holds, for each entry, the ULEB128-encoded difference
+//!   between its code offset and that of the previous entry, followed by the
+//!   ULEB128-encoded index of its rule. The first entry of each block is
+//!   relative to the code offset of the block.
+
+use zerocopy::{AsBytes, FromBytes, LayoutVerified};
+
+use crate::Entry;
+
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct CompactHeader {
+    pub rule_count: u32,
+    pub block_count: u32,
+}
+
+impl CompactHeader {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+/// An `Entry` without its code offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct Rule {
+    pub sp_offset: u32,
+    pub sp_reg: u8,
+    pub fp_offset: u8,
+    pub ra_offset: u8,
+    pub flag: u8,
+}
+
+impl Rule {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+
+    pub fn entry(&self, code_offset: u32) -> Entry {
+        Entry {
+            code_offset,
+            sp_offset: self.sp_offset,
+            sp_reg: self.sp_reg,
+            fp_offset: self.fp_offset,
+            ra_offset: self.ra_offset,
+            flag: self.flag,
+        }
+    }
+}
+
+impl From<&Entry> for Rule {
+    fn from(entry: &Entry) -> Self {
+        Self {
+            sp_offset: entry.sp_offset,
+            sp_reg: entry.sp_reg,
+            fp_offset: entry.fp_offset,
+            ra_offset: entry.ra_offset,
+            flag: entry.flag,
+        }
+    }
+}
+
+/// Start of a run of entries in the entry stream.
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct Block {
+    pub code_offset: u32,
+    /// Offset of the first entry of the block into the entry stream
+    pub stream_offset: u32,
+}
+
+impl Block {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+/// Returns `None` at the end of `bytes` or if the value overflows `u32`.
+pub fn read_uleb128(bytes: &mut &[u8]) -> Option<u32> {
+    let mut value: u32 = 0;
+    let mut shift = 0;
+
+    loop {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+
+        let bits = (byte & 0x7f) as u32;
+        let shifted = bits.checked_shl(shift)?;
+
+        if shifted >> shift != bits {
+            return None;
+        }
+
+        value |= shifted;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            break Some(value);
+        }
+    }
+}
+
+/// A compact table, decoded in place.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactTable<'a> {
+    rules: &'a [Rule],
+    blocks: &'a [Block],
+    stream: &'a [u8],
+}
+
+impl<'a> CompactTable<'a> {
+    /// Returns `None` if `bytes` is misaligned, too short for the header, rule
+    /// pool and block index it describes, or if the blocks are not sorted by
+    /// code offset with their entries in order in the stream.
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        let (header, rest) = LayoutVerified::<_, CompactHeader>::new_from_prefix(bytes)?;
+        let (rules, rest) =
+            LayoutVerified::<_, [Rule]>::new_slice_from_prefix(rest, header.rule_count as usize)?;
+        let (blocks, stream) =
+            LayoutVerified::<_, [Block]>::new_slice_from_prefix(rest, header.block_count as usize)?;
+        let blocks = blocks.into_slice();
+
+        let sorted = blocks.windows(2).all(|pair| {
+            pair[0].code_offset < pair[1].code_offset
+                && pair[0].stream_offset <= pair[1].stream_offset
+        });
+        let in_stream = blocks
+            .last()
+            .is_none_or(|last| last.stream_offset as usize <= stream.len());
+
+        if !sorted || !in_stream {
+            return None;
+        }
+
+        Some(Self {
+            rules: rules.into_slice(),
+            blocks,
+            stream,
+        })
+    }
+
+    /// Stream of the block at `index`, which ends where the next one starts.
+    fn block_stream(&self, index: usize) -> Option<&'a [u8]> {
+        let block = self.blocks.get(index)?;
+        let end = match self.blocks.get(index + 1) {
+            Some(next) => next.stream_offset as usize,
+            None => self.stream.len(),
+        };
+
+        self.stream.get(block.stream_offset as usize..end)
+    }
+
+    /// Whether every block decodes to entries with rules in the rule pool and
+    /// increasing code offsets that stay below the next block's.
+    ///
+    /// This reads the whole stream, so it is meant for checking untrusted
+    /// tables once before use.
+    pub fn validate(&self) -> bool {
+        (0..self.blocks.len()).all(|index| {
+            let Some(mut stream) = self.block_stream(index) else {
+                return false;
+            };
+            let limit = self.blocks.get(index + 1).map(|next| next.code_offset);
+            let mut code_offset = self.blocks[index].code_offset;
+            let mut first = true;
+
+            while !stream.is_empty() {
+                let (Some(delta), Some(rule)) =
+                    (read_uleb128(&mut stream), read_uleb128(&mut stream))
+                else {
+                    return false;
+                };
+
+                let Some(next) = code_offset.checked_add(delta) else {
+                    return false;
+                };
+
+                if (!first && delta == 0)
+                    || limit.is_some_and(|limit| next >= limit)
+                    || rule as usize >= self.rules.len()
+                {
+                    return false;
+                }
+
+                code_offset = next;
+                first = false;
+            }
+
+            true
+        })
+    }
+
+    /// The rule pool, which holds every rule the entries use.
+    pub fn rules(&self) -> &'a [Rule] {
+        self.rules
+    }
+
+    /// Finds the last entry whose code offset is at most `offset`.
+    pub fn find(&self, offset: u32) -> Option<Entry> {
+        let index = self.blocks.partition_point(|b| b.code_offset <= offset);
+        let index = index.checked_sub(1)?;
+        let block = self.blocks[index];
+        let mut stream = self.block_stream(index)?;
+        let mut code_offset = block.code_offset;
+        let mut found = None;
+
+        while !stream.is_empty() {
+            code_offset = code_offset.checked_add(read_uleb128(&mut stream)?)?;
+            let rule = read_uleb128(&mut stream)?;
+
+            if code_offset > offset {
+                break;
+            }
+
+            found = Some((code_offset, rule));
+        }
+
+        let (code_offset, rule) = found?;
+        Some(self.rules.get(rule as usize)?.entry(code_offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uleb128() {
+        let read = |mut bytes: &[u8]| read_uleb128(&mut bytes);
+
+        assert_eq!(read(&[0x7f]), Some(0x7f));
+        assert_eq!(read(&[0xe5, 0x8e, 0x26]), Some(624485));
+        assert_eq!(read(&[0xff, 0xff, 0xff, 0xff, 0x0f]), Some(u32::MAX));
+        assert_eq!(read(&[0x80]), None);
+        // Bits past the 32nd
+        assert_eq!(read(&[0xff, 0xff, 0xff, 0xff, 0x1f]), None);
+        assert_eq!(read(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x01]), None);
+    }
+}
@@ -0,0 +1,473 @@
+//! Self-describing container holding all tables for one binary.
+//!
+//! A container starts with a [`ContainerHeader`], followed by a directory of
+//! [`SectionHeader`] and then the section contents. Every section starts at a
+//! 4-byte aligned offset. The checksum is the CRC-32 of everything after the
+//! container header.
+
+use core::fmt;
+
+use zerocopy::{
+    byteorder::{NativeEndian, U64},
+    AsBytes, FromBytes, LayoutVerified,
+};
+
+use crate::{
+    CompactTable, Entries, Entry, ExtEntry, Lsda, Lsdas, Module, PageIndex, Symbol, SymbolTable,
+    WideEntry,
+};
+
+pub const MAGIC: [u8; 8] = *b"RVINDTBL";
+pub const VERSION: u32 = 1;
+
+/// Unwind table with fixed-size `Entry` records
+pub const SECTION_UNWIND: u32 = 1;
+/// Unwind table in the compact format
+pub const SECTION_UNWIND_COMPACT: u32 = 2;
+/// Symbol records, see `SymbolTable`
+pub const SECTION_SYMBOLS: u32 = 3;
+/// Symbol names, see `SymbolTable`
+pub const SECTION_STRINGS: u32 = 4;
+/// Unwind table with fixed-size `ExtEntry` records
+pub const SECTION_UNWIND_EXTENDED: u32 = 5;
+/// The LSDA of each function that has one, see `Lsdas`
+pub const SECTION_LSDA: u32 = 6;
+/// Index over the fixed or extended unwind table, see `PageIndex`
+pub const SECTION_PAGE_INDEX: u32 = 7;
+/// Save offsets for unwind entries with `Entry::FLAG_WIDE`, see `WideEntry`
+pub const SECTION_WIDE: u32 = 8;
+/// Bounds of the described code, see `TextBounds`
+pub const SECTION_TEXT: u32 = 9;
+
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct ContainerHeader {
+    pub magic: [u8; 8],
+    pub version: u32,
+    /// Size of the whole container in bytes
+    pub total_len: u32,
+    pub section_count: u32,
+    pub checksum: u32,
+}
+
+impl ContainerHeader {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct SectionHeader {
+    pub kind: u32,
+    /// Offset from the start of the container
+    pub offset: u32,
+    pub len: u32,
+}
+
+impl SectionHeader {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+/// Link-time address and size of the code that all code offsets are
+/// relative to.
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct TextBounds {
+    pub start: U64<NativeEndian>,
+    pub len: U64<NativeEndian>,
+}
+
+impl TextBounds {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+/// CRC-32 as used by zlib and PNG.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableError {
+    /// The container is not 4-byte aligned
+    Misaligned,
+    /// Too short for the container header
+    TooShort,
+    BadMagic,
+    UnsupportedVersion(u32),
+    /// The length in the header does not match the data
+    LengthMismatch {
+        expected: u32,
+        actual: usize,
+    },
+    ChecksumMismatch {
+        expected: u32,
+        actual: u32,
+    },
+    /// A section lies outside the container or is misaligned
+    BadSection {
+        kind: u32,
+    },
+    DuplicateSection {
+        kind: u32,
+    },
+    /// Two sections that cannot both be present, such as two unwind tables in
+    /// different formats
+    ConflictingSections {
+        first: u32,
+        second: u32,
+    },
+    /// There is no unwind table
+    MissingUnwind,
+    /// There are no text bounds
+    MissingText,
+    /// Only one of the symbol and string sections is present
+    IncompleteSymbols,
+    UnsortedEntries,
+    UnsortedSymbols,
+    /// LSDA records are not sorted, or their functions overlap
+    UnsortedLsdas,
+    /// The page index is for a different or compact unwind table
+    IndexMismatch,
+    /// Some entries have `Entry::FLAG_WIDE`, but there is no wide section
+    MissingWide,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TableError::*;
+
+        match self {
+            Misaligned => write!(f, "container misaligned"),
+            TooShort => write!(f, "container too short"),
+            BadMagic => write!(f, "bad magic"),
+            UnsupportedVersion(version) => write!(f, "unsupported version {version}"),
+            LengthMismatch { expected, actual } => {
+                write!(f, "length {actual} does not match header length {expected}")
+            }
+            ChecksumMismatch { expected, actual } => {
+                write!(f, "checksum {actual:#010x} does not match {expected:#010x}")
+            }
+            BadSection { kind } => write!(f, "bad section of kind {kind}"),
+            DuplicateSection { kind } => write!(f, "duplicate section of kind {kind}"),
+            ConflictingSections { first, second } => {
+                write!(f, "sections of kinds {first} and {second} conflict")
+            }
+            MissingUnwind => write!(f, "no unwind table"),
+            MissingText => write!(f, "no text bounds"),
+            IncompleteSymbols => write!(f, "symbol table without strings or vice versa"),
+            UnsortedEntries => write!(f, "unwind entries not sorted"),
+            UnsortedSymbols => write!(f, "symbols not sorted"),
+            UnsortedLsdas => write!(f, "LSDA records not sorted"),
+            IndexMismatch => write!(f, "page index does not match unwind table"),
+            MissingWide => write!(f, "wide entries without save offsets"),
+        }
+    }
+}
+
+/// The validated contents of a container.
+#[derive(Debug, Clone, Copy)]
+pub struct Table<'a> {
+    text_start: usize,
+    text_len: usize,
+    entries: Entries<'a>,
+    symbols: Option<SymbolTable<'a>>,
+    lsdas: Option<Lsdas<'a>>,
+    page_index: Option<PageIndex<'a>>,
+    wide: &'a [WideEntry],
+}
+
+/// Casts an unwind section to its records and checks that they are sorted.
+fn sorted_entries<T: FromBytes>(
+    data: &[u8],
+    kind: u32,
+    code_offset: impl Fn(&T) -> u32,
+) -> Result<&[T], TableError> {
+    let entries = LayoutVerified::<_, [T]>::new_slice(data)
+        .ok_or(TableError::BadSection { kind })?
+        .into_slice();
+
+    if !entries
+        .windows(2)
+        .all(|w| code_offset(&w[0]) <= code_offset(&w[1]))
+    {
+        return Err(TableError::UnsortedEntries);
+    }
+
+    Ok(entries)
+}
+
+/// Whether any entry needs a `WideEntry` for its save offsets.
+fn needs_wide(entries: &Entries) -> bool {
+    let wide = |flag: u8| (flag & Entry::FLAG_WIDE) != 0;
+
+    match entries {
+        Entries::Fixed(entries) => entries.iter().any(|e| wide(e.flag)),
+        Entries::Compact(table) => table.rules().iter().any(|r| wide(r.flag)),
+        Entries::Extended(entries) => entries.iter().any(|e| wide(e.entry.flag)),
+    }
+}
+
+impl<'a> Table<'a> {
+    /// Parses and validates a container.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, TableError> {
+        use TableError::*;
+
+        if !(bytes.as_ptr() as usize).is_multiple_of(4) {
+            return Err(Misaligned);
+        }
+
+        let (header, body) =
+            LayoutVerified::<_, ContainerHeader>::new_from_prefix(bytes).ok_or(TooShort)?;
+
+        if header.magic != MAGIC {
+            return Err(BadMagic);
+        }
+
+        if header.version != VERSION {
+            return Err(UnsupportedVersion(header.version));
+        }
+
+        if header.total_len as usize != bytes.len() {
+            return Err(LengthMismatch {
+                expected: header.total_len,
+                actual: bytes.len(),
+            });
+        }
+
+        let checksum = crc32(body);
+        if header.checksum != checksum {
+            return Err(ChecksumMismatch {
+                expected: header.checksum,
+                actual: checksum,
+            });
+        }
+
+        let (sections, _) = LayoutVerified::<_, [SectionHeader]>::new_slice_from_prefix(
+            body,
+            header.section_count as usize,
+        )
+        .ok_or(TooShort)?;
+
+        let mut unwind: Option<&'a [u8]> = None;
+        let mut compact: Option<&'a [u8]> = None;
+        let mut extended: Option<&'a [u8]> = None;
+        let mut lsdas: Option<&'a [u8]> = None;
+        let mut page_index: Option<&'a [u8]> = None;
+        let mut wide: Option<&'a [u8]> = None;
+        let mut text: Option<&'a [u8]> = None;
+        let mut sym: Option<&'a [u8]> = None;
+        let mut strings: Option<&'a [u8]> = None;
+
+        for section in sections.into_slice() {
+            let kind = section.kind;
+            let start = section.offset as usize;
+            let end = start.checked_add(section.len as usize);
+
+            let data = match end.and_then(|end| bytes.get(start..end)) {
+                Some(data) if start.is_multiple_of(4) => data,
+                _ => return Err(BadSection { kind }),
+            };
+
+            let slot = match kind {
+                SECTION_UNWIND => &mut unwind,
+                SECTION_UNWIND_COMPACT => &mut compact,
+                SECTION_SYMBOLS => &mut sym,
+                SECTION_STRINGS => &mut strings,
+                SECTION_UNWIND_EXTENDED => &mut extended,
+                SECTION_LSDA => &mut lsdas,
+                SECTION_PAGE_INDEX => &mut page_index,
+                SECTION_WIDE => &mut wide,
+                SECTION_TEXT => &mut text,
+                // Unknown sections are skipped for forward compatibility
+                _ => continue,
+            };
+
+            if slot.replace(data).is_some() {
+                return Err(DuplicateSection { kind });
+            }
+        }
+
+        let text = LayoutVerified::<_, TextBounds>::new(text.ok_or(MissingText)?)
+            .ok_or(BadSection { kind: SECTION_TEXT })?;
+        let (Ok(text_start), Ok(text_len)) =
+            (text.start.get().try_into(), text.len.get().try_into())
+        else {
+            return Err(BadSection { kind: SECTION_TEXT });
+        };
+
+        let entries = match (unwind, compact, extended) {
+            (Some(unwind), None, None) => {
+                Entries::Fixed(sorted_entries(unwind, SECTION_UNWIND, |e: &Entry| {
+                    e.code_offset
+                })?)
+            }
+            (None, Some(compact), None) => {
+                match CompactTable::from_bytes(compact).filter(|table| table.validate()) {
+                    Some(table) => Entries::Compact(table),
+                    None => {
+                        return Err(BadSection {
+                            kind: SECTION_UNWIND_COMPACT,
+                        })
+                    }
+                }
+            }
+            (None, None, Some(extended)) => Entries::Extended(sorted_entries(
+                extended,
+                SECTION_UNWIND_EXTENDED,
+                |e: &ExtEntry| e.entry.code_offset,
+            )?),
+            (None, None, None) => return Err(MissingUnwind),
+            (Some(_), Some(_), _) => {
+                return Err(ConflictingSections {
+                    first: SECTION_UNWIND,
+                    second: SECTION_UNWIND_COMPACT,
+                })
+            }
+            (Some(_), _, Some(_)) => {
+                return Err(ConflictingSections {
+                    first: SECTION_UNWIND,
+                    second: SECTION_UNWIND_EXTENDED,
+                })
+            }
+            (_, Some(_), Some(_)) => {
+                return Err(ConflictingSections {
+                    first: SECTION_UNWIND_COMPACT,
+                    second: SECTION_UNWIND_EXTENDED,
+                })
+            }
+        };
+
+        let symbols = match (sym, strings) {
+            (Some(sym), Some(strings)) => {
+                let symbols = LayoutVerified::<_, [Symbol]>::new_slice(sym)
+                    .ok_or(BadSection {
+                        kind: SECTION_SYMBOLS,
+                    })?
+                    .into_slice();
+
+                if !symbols
+                    .windows(2)
+                    .all(|w| w[0].code_offset <= w[1].code_offset)
+                {
+                    return Err(UnsortedSymbols);
+                }
+
+                Some(SymbolTable::new(symbols, strings))
+            }
+            (None, None) => None,
+            _ => return Err(IncompleteSymbols),
+        };
+
+        let lsdas = match lsdas {
+            Some(lsdas) => {
+                let records = LayoutVerified::<_, [Lsda]>::new_slice(lsdas)
+                    .ok_or(BadSection { kind: SECTION_LSDA })?
+                    .into_slice();
+
+                if !records
+                    .windows(2)
+                    .all(|w| w[0].code_offset.saturating_add(w[0].code_len) <= w[1].code_offset)
+                {
+                    return Err(UnsortedLsdas);
+                }
+
+                Some(Lsdas::new(records))
+            }
+            None => None,
+        };
+
+        let page_index = match page_index {
+            Some(page_index) => {
+                let index = PageIndex::from_bytes(page_index).ok_or(BadSection {
+                    kind: SECTION_PAGE_INDEX,
+                })?;
+
+                if entries.entry_count() != Some(index.entry_count()) {
+                    return Err(IndexMismatch);
+                }
+
+                Some(index)
+            }
+            None => None,
+        };
+
+        let wide = match wide {
+            Some(wide) => sorted_entries(wide, SECTION_WIDE, |e: &WideEntry| e.code_offset)?,
+            None if needs_wide(&entries) => return Err(MissingWide),
+            None => &[],
+        };
+
+        Ok(Self {
+            text_start,
+            text_len,
+            entries,
+            symbols,
+            lsdas,
+            page_index,
+            wide,
+        })
+    }
+
+    /// Link-time address of the described code
+    pub fn text_start(&self) -> usize {
+        self.text_start
+    }
+
+    /// Size of the described code in bytes
+    pub fn text_len(&self) -> usize {
+        self.text_len
+    }
+
+    /// A module for the tables, with the code loaded `load_bias` bytes above
+    /// its link-time address.
+    pub fn module(&self, load_bias: usize) -> Module<'a> {
+        Module {
+            text_start: self.text_start,
+            text_len: self.text_len,
+            load_bias,
+            entries: self.entries,
+            index: self.page_index,
+            wide: self.wide,
+        }
+    }
+
+    pub fn entries(&self) -> Entries<'a> {
+        self.entries
+    }
+
+    pub fn symbols(&self) -> Option<SymbolTable<'a>> {
+        self.symbols
+    }
+
+    pub fn lsdas(&self) -> Option<Lsdas<'a>> {
+        self.lsdas
+    }
+
+    pub fn page_index(&self) -> Option<PageIndex<'a>> {
+        self.page_index
+    }
+
+    /// Save offsets for the entries with `Entry::FLAG_WIDE`, empty if there
+    /// are none.
+    pub fn wide(&self) -> &'a [WideEntry] {
+        self.wide
+    }
+}
@@ -0,0 +1,755 @@
+//! A panic=unwind runtime for riscv64, implementing the `_Unwind_*` functions
+//! that panic runtimes, personality routines and landing pads call.
+//!
+//! Exceptions are raised in the two phases of the Itanium C++ ABI. The search
+//! phase calls the personality routine for each frame whose function has an
+//! LSDA until it reports a handler, and the cleanup phase calls it again for
+//! each frame on the way there, installing the landing pads it picks. The
+//! LSDAs are located with the [`Lsdas`] written by `rvind-analyze
+//! --lsda`, and every function uses the personality routine given in
+//! [`EhTables`], normally `rust_eh_personality`.
+//!
+//! Landing pads expect the callee-saved registers of their frame to be
+//! restored, so the unwind table must be in the extended format. Unwinding
+//! fails if a landing pad is reached with any of `s1` through `s11` lost.
+//! Floating point registers are not restored.
+
+#![allow(non_snake_case)]
+
+#[cfg(not(test))]
+use core::arch::global_asm;
+use core::{
+    ffi::{c_int, c_void},
+    ops::BitOr,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::{
+    CallFrame, DirectMemory, FirstFrame, FrameSource, Lsdas, Module, Unwinder, SAVED_REGS,
+    SAVED_REG_NUMBERS,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct UnwindReasonCode(pub c_int);
+
+impl UnwindReasonCode {
+    pub const NO_REASON: Self = Self(0);
+    pub const FOREIGN_EXCEPTION_CAUGHT: Self = Self(1);
+    pub const FATAL_PHASE2_ERROR: Self = Self(2);
+    pub const FATAL_PHASE1_ERROR: Self = Self(3);
+    pub const NORMAL_STOP: Self = Self(4);
+    pub const END_OF_STACK: Self = Self(5);
+    pub const HANDLER_FOUND: Self = Self(6);
+    pub const INSTALL_CONTEXT: Self = Self(7);
+    pub const CONTINUE_UNWIND: Self = Self(8);
+}
+
+/// What a personality routine is called for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct UnwindAction(pub c_int);
+
+impl UnwindAction {
+    pub const SEARCH_PHASE: Self = Self(1);
+    pub const CLEANUP_PHASE: Self = Self(2);
+    pub const HANDLER_FRAME: Self = Self(4);
+    pub const FORCE_UNWIND: Self = Self(8);
+    pub const END_OF_STACK: Self = Self(16);
+}
+
+impl BitOr for UnwindAction {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+pub type ExceptionCleanup = unsafe extern "C" fn(UnwindReasonCode, *mut UnwindException);
+
+pub type Personality = unsafe extern "C" fn(
+    version: c_int,
+    actions: UnwindAction,
+    exception_class: u64,
+    exception: *mut UnwindException,
+    context: *mut UnwindContext,
+) -> UnwindReasonCode;
+
+#[repr(C)]
+pub struct UnwindException {
+    pub exception_class: u64,
+    pub exception_cleanup: Option<ExceptionCleanup>,
+    /// Reserved for the runtime, which keeps the sp of the handler frame in
+    /// the second word
+    pub private: [usize; 2],
+}
+
+/// A frame passed to a personality routine or an `_Unwind_Backtrace`
+/// callback.
+pub struct UnwindContext {
+    frame: CallFrame,
+    /// Start of the function, if it has an LSDA
+    region_start: usize,
+    /// Address of the LSDA, or zero if there is none
+    lsda: usize,
+    /// pc and `x1` through `x31` to install for a landing pad, as changed by
+    /// `_Unwind_SetIP` and `_Unwind_SetGR`
+    regs: [usize; 32],
+}
+
+impl UnwindContext {
+    fn new(tables: &EhTables, frame: CallFrame) -> Self {
+        let mut regs = [0; 32];
+        regs[0] = frame.pc;
+        // As if the call had just returned
+        regs[1] = frame.pc;
+        regs[2] = frame.sp;
+        regs[8] = frame.fp;
+
+        for (reg, value) in SAVED_REG_NUMBERS.into_iter().zip(frame.saved) {
+            regs[reg] = value.unwrap_or(0);
+        }
+
+        let (region_start, lsda) = tables.lsda(frame.pc).unwrap_or((0, 0));
+
+        Self {
+            frame,
+            region_start,
+            lsda,
+            regs,
+        }
+    }
+}
+
+pub type TraceFn = extern "C" fn(*mut UnwindContext, *mut c_void) -> UnwindReasonCode;
+
+extern "C" {
+    /// Unwinds to the innermost `catch_unwind`, running cleanups on the way.
+    /// Only returns if there is no such handler, or on error.
+    pub fn _Unwind_RaiseException(exception: *mut UnwindException) -> UnwindReasonCode;
+
+    /// Continues unwinding at the end of a cleanup landing pad.
+    pub fn _Unwind_Resume(exception: *mut UnwindException) -> !;
+
+    /// Calls `trace` for each frame, starting with the caller.
+    pub fn _Unwind_Backtrace(trace: TraceFn, arg: *mut c_void) -> UnwindReasonCode;
+
+    /// Loads pc and `x1` through `x31` from `regs` and jumps to pc, restoring
+    /// only ra, sp, the callee-saved registers, a0 and a1.
+    fn __rvind_install_context(regs: &[usize; 32]) -> !;
+}
+
+/// The tables describing all code that panics can unwind through.
+pub struct EhTables<'a> {
+    /// Should have `Entries::Extended`, see the module documentation
+    pub module: Module<'a>,
+    pub lsdas: Lsdas<'a>,
+    /// Called for every function with an LSDA
+    pub personality: Personality,
+}
+
+static TABLES: AtomicPtr<EhTables<'static>> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets the tables used by all later unwinds.
+pub fn register(tables: &'static EhTables<'static>) {
+    TABLES.store(
+        tables as *const EhTables as *mut EhTables,
+        Ordering::Release,
+    );
+}
+
+#[cfg(not(test))]
+fn tables() -> Option<&'static EhTables<'static>> {
+    // SAFETY: Only `&'static EhTables` are ever stored
+    unsafe { TABLES.load(Ordering::Acquire).as_ref() }
+}
+
+impl EhTables<'_> {
+    /// Finds the start and the LSDA address of the function that the call
+    /// returning to `pc` is in.
+    fn lsda(&self, pc: usize) -> Option<(usize, usize)> {
+        // The return address may be just past the end of the function
+        let offset = self.module.code_offset(pc.wrapping_sub(1))?;
+        let lsda = self.lsdas.find(offset.try_into().ok()?)?;
+        let text_start = self.module.text_start.wrapping_add(self.module.load_bias);
+
+        Some((
+            text_start.wrapping_add(lsda.code_offset as usize),
+            lsda.address(text_start),
+        ))
+    }
+
+    /// # Safety
+    ///
+    /// `exception` must point to a valid exception.
+    unsafe fn personality(
+        &self,
+        actions: UnwindAction,
+        exception: *mut UnwindException,
+        context: &mut UnwindContext,
+    ) -> UnwindReasonCode {
+        unsafe { (self.personality)(1, actions, (*exception).exception_class, exception, context) }
+    }
+}
+
+/// Registers saved on entry to the runtime, see `save_registers`
+#[repr(C)]
+struct Registers {
+    ra: usize,
+    sp: usize,
+    /// `s0` through `s11`
+    s: [usize; 12],
+}
+
+fn frames<'a>(tables: &'a EhTables, regs: &Registers) -> Unwinder<'a, Module<'a>, DirectMemory> {
+    let mut saved = [None; SAVED_REGS];
+
+    for (value, s) in saved.iter_mut().zip(&regs.s[1..]) {
+        *value = Some(*s);
+    }
+
+    let first_frame = FirstFrame {
+        // Clobbered by the call into the runtime
+        ra: 0,
+        frame: CallFrame {
+            pc: regs.ra,
+            sp: regs.sp,
+            fp: regs.s[0],
+            saved,
+            source: FrameSource::Start,
+        },
+        regs: None,
+        // The caller of the runtime, which may never be returned to
+        return_address: true,
+    };
+
+    // SAFETY: Only the frames of the current stack are read
+    let memory = unsafe { DirectMemory::new() };
+
+    Unwinder::new(&tables.module, first_frame, memory)
+}
+
+/// Finds the sp of the frame whose personality routine catches the exception.
+///
+/// # Safety
+///
+/// `exception` must point to a valid exception.
+unsafe fn search(
+    tables: &EhTables,
+    exception: *mut UnwindException,
+    regs: &Registers,
+) -> Result<usize, UnwindReasonCode> {
+    for frame in frames(tables, regs) {
+        let frame = frame.map_err(|_| UnwindReasonCode::FATAL_PHASE1_ERROR)?;
+        let mut context = UnwindContext::new(tables, frame);
+
+        if context.lsda == 0 {
+            continue;
+        }
+
+        match unsafe { tables.personality(UnwindAction::SEARCH_PHASE, exception, &mut context) } {
+            UnwindReasonCode::HANDLER_FOUND => return Ok(frame.sp),
+            UnwindReasonCode::CONTINUE_UNWIND => {}
+            _ => return Err(UnwindReasonCode::FATAL_PHASE1_ERROR),
+        }
+    }
+
+    Err(UnwindReasonCode::END_OF_STACK)
+}
+
+/// Finds the next landing pad on the way to the handler frame, and returns the
+/// registers to install for it.
+///
+/// # Safety
+///
+/// `exception` must have been through a successful search.
+unsafe fn cleanup(
+    tables: &EhTables,
+    exception: *mut UnwindException,
+    regs: &Registers,
+) -> Result<[usize; 32], UnwindReasonCode> {
+    let handler_sp = unsafe { (*exception).private[1] };
+
+    for frame in frames(tables, regs) {
+        let frame = frame.map_err(|_| UnwindReasonCode::FATAL_PHASE2_ERROR)?;
+        let mut context = UnwindContext::new(tables, frame);
+
+        if context.lsda == 0 {
+            continue;
+        }
+
+        let handler = frame.sp == handler_sp;
+        let actions = if handler {
+            UnwindAction::CLEANUP_PHASE | UnwindAction::HANDLER_FRAME
+        } else {
+            UnwindAction::CLEANUP_PHASE
+        };
+
+        match unsafe { tables.personality(actions, exception, &mut context) } {
+            UnwindReasonCode::INSTALL_CONTEXT => {}
+            UnwindReasonCode::CONTINUE_UNWIND if !handler => continue,
+            _ => return Err(UnwindReasonCode::FATAL_PHASE2_ERROR),
+        }
+
+        if frame.saved.contains(&None) {
+            return Err(UnwindReasonCode::FATAL_PHASE2_ERROR);
+        }
+
+        return Ok(context.regs);
+    }
+
+    Err(UnwindReasonCode::FATAL_PHASE2_ERROR)
+}
+
+#[cfg(not(test))]
+unsafe extern "C" fn rvind_raise_exception(
+    exception: *mut UnwindException,
+    regs: &Registers,
+) -> UnwindReasonCode {
+    // Without tables nothing can be unwound, which is a setup error
+    let Some(tables) = tables() else {
+        return UnwindReasonCode::FATAL_PHASE1_ERROR;
+    };
+
+    match unsafe { search(tables, exception, regs) } {
+        Ok(handler_sp) => unsafe { (*exception).private = [0, handler_sp] },
+        Err(code) => return code,
+    }
+
+    match unsafe { cleanup(tables, exception, regs) } {
+        // SAFETY: The landing pad runs in the frame it belongs to
+        Ok(regs) => unsafe { __rvind_install_context(&regs) },
+        Err(code) => code,
+    }
+}
+
+#[cfg(not(test))]
+unsafe extern "C" fn rvind_resume(
+    exception: *mut UnwindException,
+    regs: &Registers,
+) -> UnwindReasonCode {
+    let Some(tables) = tables() else {
+        return UnwindReasonCode::FATAL_PHASE2_ERROR;
+    };
+
+    match unsafe { cleanup(tables, exception, regs) } {
+        // SAFETY: The landing pad runs in the frame it belongs to
+        Ok(regs) => unsafe { __rvind_install_context(&regs) },
+        Err(code) => code,
+    }
+}
+
+#[cfg(not(test))]
+extern "C" fn rvind_backtrace(
+    trace: TraceFn,
+    arg: *mut c_void,
+    regs: &Registers,
+) -> UnwindReasonCode {
+    let Some(tables) = tables() else {
+        return UnwindReasonCode::END_OF_STACK;
+    };
+
+    for frame in frames(tables, regs) {
+        let Ok(frame) = frame else {
+            return UnwindReasonCode::FATAL_PHASE1_ERROR;
+        };
+
+        let mut context = UnwindContext::new(tables, frame);
+
+        if trace(&mut context, arg) != UnwindReasonCode::NO_REASON {
+            return UnwindReasonCode::FATAL_PHASE1_ERROR;
+        }
+    }
+
+    UnwindReasonCode::END_OF_STACK
+}
+
+/// Saves the caller's registers as `Registers` on the stack
+#[cfg(not(test))]
+macro_rules! save_registers {
+    () => {
+        "
+        addi sp, sp, -112
+        sd ra, 0(sp)
+        addi t0, sp, 112
+        sd t0, 8(sp)
+        sd s0, 16(sp)
+        sd s1, 24(sp)
+        sd s2, 32(sp)
+        sd s3, 40(sp)
+        sd s4, 48(sp)
+        sd s5, 56(sp)
+        sd s6, 64(sp)
+        sd s7, 72(sp)
+        sd s8, 80(sp)
+        sd s9, 88(sp)
+        sd s10, 96(sp)
+        sd s11, 104(sp)
+        "
+    };
+}
+
+#[cfg(not(test))]
+global_asm!(
+    ".globl _Unwind_RaiseException",
+    ".type _Unwind_RaiseException, @function",
+    "_Unwind_RaiseException:",
+    save_registers!(),
+    "mv a1, sp",
+    "call {raise}",
+    "ld ra, 0(sp)",
+    "addi sp, sp, 112",
+    "ret",
+    ".size _Unwind_RaiseException, . - _Unwind_RaiseException",
+    "",
+    ".globl _Unwind_Resume",
+    ".type _Unwind_Resume, @function",
+    "_Unwind_Resume:",
+    save_registers!(),
+    "mv a1, sp",
+    "call {resume}",
+    "unimp",
+    ".size _Unwind_Resume, . - _Unwind_Resume",
+    "",
+    ".globl _Unwind_Backtrace",
+    ".type _Unwind_Backtrace, @function",
+    "_Unwind_Backtrace:",
+    save_registers!(),
+    "mv a2, sp",
+    "call {backtrace}",
+    "ld ra, 0(sp)",
+    "addi sp, sp, 112",
+    "ret",
+    ".size _Unwind_Backtrace, . - _Unwind_Backtrace",
+    "",
+    ".globl __rvind_install_context",
+    ".hidden __rvind_install_context",
+    ".type __rvind_install_context, @function",
+    "__rvind_install_context:",
+    "ld t0, 0(a0)",
+    "ld ra, 8(a0)",
+    "ld sp, 16(a0)",
+    "ld s0, 64(a0)",
+    "ld s1, 72(a0)",
+    "ld s2, 144(a0)",
+    "ld s3, 152(a0)",
+    "ld s4, 160(a0)",
+    "ld s5, 168(a0)",
+    "ld s6, 176(a0)",
+    "ld s7, 184(a0)",
+    "ld s8, 192(a0)",
+    "ld s9, 200(a0)",
+    "ld s10, 208(a0)",
+    "ld s11, 216(a0)",
+    "ld a1, 88(a0)",
+    "ld a0, 80(a0)",
+    "jr t0",
+    ".size __rvind_install_context, . - __rvind_install_context",
+    raise = sym rvind_raise_exception,
+    resume = sym rvind_resume,
+    backtrace = sym rvind_backtrace,
+);
+
+/// # Safety
+///
+/// `exception` must point to a valid exception.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_DeleteException(exception: *mut UnwindException) {
+    if let Some(cleanup) = unsafe { (*exception).exception_cleanup } {
+        unsafe { cleanup(UnwindReasonCode::FOREIGN_EXCEPTION_CAUGHT, exception) }
+    }
+}
+
+/// # Safety
+///
+/// `context` must be the context passed to a personality routine or an
+/// `_Unwind_Backtrace` callback.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_GetIP(context: *mut UnwindContext) -> usize {
+    unsafe { (*context).regs[0] }
+}
+
+/// # Safety
+///
+/// Like `_Unwind_GetIP`, and `ip_before_insn` must be valid for writes.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_GetIPInfo(
+    context: *mut UnwindContext,
+    ip_before_insn: *mut c_int,
+) -> usize {
+    unsafe {
+        // Every frame after the first is a return address
+        *ip_before_insn = 0;
+        (*context).regs[0]
+    }
+}
+
+/// Sets the pc the landing pad is installed at.
+///
+/// # Safety
+///
+/// Like `_Unwind_GetIP`.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_SetIP(context: *mut UnwindContext, value: usize) {
+    unsafe { (*context).regs[0] = value }
+}
+
+/// Returns `x{index}`, or zero for registers that could not be recovered.
+///
+/// Only ra, sp, fp and `s1` through `s11` are known, along with the registers
+/// set by `_Unwind_SetGR`.
+///
+/// # Safety
+///
+/// Like `_Unwind_GetIP`.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_GetGR(context: *mut UnwindContext, index: c_int) -> usize {
+    match index {
+        1..=31 => unsafe { (*context).regs[index as usize] },
+        _ => 0,
+    }
+}
+
+/// Sets `x{index}` for the landing pad. Of the registers that are not
+/// callee-saved, only a0 and a1 are installed.
+///
+/// # Safety
+///
+/// Like `_Unwind_GetIP`.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_SetGR(context: *mut UnwindContext, index: c_int, value: usize) {
+    if let 1..=31 = index {
+        unsafe { (*context).regs[index as usize] = value }
+    }
+}
+
+/// Returns the sp of the frame, like LLVM's libunwind.
+///
+/// # Safety
+///
+/// Like `_Unwind_GetIP`.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_GetCFA(context: *mut UnwindContext) -> usize {
+    unsafe { (*context).frame.sp }
+}
+
+/// Returns the LSDA of the function, or zero if it has none.
+///
+/// # Safety
+///
+/// Like `_Unwind_GetIP`.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_GetLanguageSpecificData(context: *mut UnwindContext) -> usize {
+    unsafe { (*context).lsda }
+}
+
+/// Returns the start of the function, or zero if it has no LSDA.
+///
+/// # Safety
+///
+/// Like `_Unwind_GetIP`.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn _Unwind_GetRegionStart(context: *mut UnwindContext) -> usize {
+    unsafe { (*context).region_start }
+}
+
+/// Always zero, as pointers relative to the text base are not used on RISC-V.
+#[cfg_attr(not(test), no_mangle)]
+pub extern "C" fn _Unwind_GetTextRelBase(_context: *mut UnwindContext) -> usize {
+    0
+}
+
+/// Always zero, as pointers relative to the data base are not used on RISC-V.
+#[cfg_attr(not(test), no_mangle)]
+pub extern "C" fn _Unwind_GetDataRelBase(_context: *mut UnwindContext) -> usize {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Context, Entry, ExtEntry, Lsda};
+
+    /// A stand-in for an LSDA with a single call site, all relative to the
+    /// start of the function
+    #[repr(C)]
+    struct CallSite {
+        start: usize,
+        len: usize,
+        landing_pad: usize,
+        catch: bool,
+    }
+
+    /// Like a real personality routine, but for `CallSite` LSDAs. Passes the
+    /// exception in a0 and the actions in a1 to the landing pad.
+    unsafe extern "C" fn personality(
+        _version: c_int,
+        actions: UnwindAction,
+        _exception_class: u64,
+        exception: *mut UnwindException,
+        context: *mut UnwindContext,
+    ) -> UnwindReasonCode {
+        unsafe {
+            let call_site = &*(_Unwind_GetLanguageSpecificData(context) as *const CallSite);
+            let region_start = _Unwind_GetRegionStart(context);
+            let offset = _Unwind_GetIP(context) - 1 - region_start;
+
+            if offset.wrapping_sub(call_site.start) >= call_site.len {
+                return UnwindReasonCode::CONTINUE_UNWIND;
+            }
+
+            if actions.0 & UnwindAction::SEARCH_PHASE.0 != 0 {
+                return match call_site.catch {
+                    true => UnwindReasonCode::HANDLER_FOUND,
+                    false => UnwindReasonCode::CONTINUE_UNWIND,
+                };
+            }
+
+            _Unwind_SetGR(context, 10, exception as usize);
+            _Unwind_SetGR(context, 11, actions.0 as usize);
+            _Unwind_SetIP(context, region_start + call_site.landing_pad);
+            UnwindReasonCode::INSTALL_CONTEXT
+        }
+    }
+
+    /// A function with a 16-byte frame saving ra, after a 4-byte prologue.
+    fn function(code_offset: u32) -> [ExtEntry; 2] {
+        let entry = |code_offset, sp_offset, ra_offset| {
+            ExtEntry::from(Entry {
+                code_offset,
+                sp_offset,
+                sp_reg: 2,
+                fp_offset: u8::MAX,
+                ra_offset,
+                flag: Entry::FLAG_VALID,
+            })
+        };
+
+        [
+            entry(code_offset, 0, u8::MAX),
+            entry(code_offset + 4, 16, 8),
+        ]
+    }
+
+    /// Runs `f` with the tables for four functions of 0x20 bytes each, where
+    /// each calls the one before it at offset 8:
+    ///
+    /// - The first raises the exception.
+    /// - The second has a cleanup landing pad at offset 0x10, which calls
+    ///   `_Unwind_Resume` as its last instruction.
+    /// - The third catches the exception with a landing pad at offset 0x14.
+    /// - The fourth is the outermost function.
+    ///
+    /// Also passes the stack of the first function and the start of text.
+    fn with_tables(f: impl FnOnce(&EhTables, &[usize; 8], usize)) {
+        let call_sites = [
+            CallSite {
+                start: 8,
+                len: 4,
+                landing_pad: 0x10,
+                catch: false,
+            },
+            CallSite {
+                start: 8,
+                len: 4,
+                landing_pad: 0x14,
+                catch: true,
+            },
+        ];
+        // Not code, but close enough for the offsets of the LSDAs to fit
+        let text_start = call_sites.as_ptr() as usize - 0x1000;
+        let lsda = |code_offset, call_site: &CallSite| Lsda {
+            code_offset,
+            code_len: 0x20,
+            lsda: (call_site as *const CallSite as usize - text_start) as u32,
+            reserved: 0,
+        };
+        let lsdas = [lsda(0x20, &call_sites[0]), lsda(0x40, &call_sites[1])];
+
+        let entries = [function(0), function(0x20), function(0x40), function(0x60)];
+        let entries = entries.as_flattened();
+        let context = Context {
+            text_start,
+            text_len: 0x80,
+        };
+
+        let tables = EhTables {
+            module: Module::new(&context, entries),
+            lsdas: Lsdas::new(&lsdas),
+            personality,
+        };
+        let stack = [
+            0,
+            text_start + 0x2c,
+            0,
+            text_start + 0x4c,
+            0,
+            text_start + 0x6c,
+            0,
+            0,
+        ];
+
+        f(&tables, &stack, text_start);
+    }
+
+    fn registers(ra: usize, sp: usize) -> Registers {
+        Registers {
+            ra,
+            sp,
+            s: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+
+    fn exception() -> UnwindException {
+        UnwindException {
+            exception_class: 0,
+            exception_cleanup: None,
+            private: [0; 2],
+        }
+    }
+
+    #[test]
+    fn search_finds_handler() {
+        with_tables(|tables, stack, text_start| {
+            let sp = stack.as_ptr() as usize;
+            let regs = registers(text_start + 0x10, sp);
+            let mut exception = exception();
+
+            let handler_sp = unsafe { search(tables, &mut exception, &regs) };
+
+            assert_eq!(handler_sp, Ok(sp + 32));
+        });
+    }
+
+    #[test]
+    fn cleanup_and_resume() {
+        with_tables(|tables, stack, text_start| {
+            let sp = stack.as_ptr() as usize;
+            let mut exception = exception();
+            exception.private[1] = sp + 32;
+
+            // Raised in the first function, which has no landing pad
+            let regs = registers(text_start + 0x10, sp);
+            let landing_pad = unsafe { cleanup(tables, &mut exception, &regs) }.unwrap();
+
+            assert_eq!(landing_pad[0], text_start + 0x30);
+            assert_eq!(landing_pad[1], text_start + 0x2c);
+            assert_eq!(landing_pad[2], sp + 16);
+            assert_eq!(landing_pad[9], 1);
+            assert_eq!(landing_pad[10], &mut exception as *mut _ as usize);
+            assert_eq!(landing_pad[11], UnwindAction::CLEANUP_PHASE.0 as usize);
+            assert_eq!(landing_pad[27], 11);
+
+            // Resumed from the end of the second function
+            let regs = registers(text_start + 0x40, sp + 16);
+            let handler = unsafe { cleanup(tables, &mut exception, &regs) }.unwrap();
+            let actions = UnwindAction::CLEANUP_PHASE | UnwindAction::HANDLER_FRAME;
+
+            assert_eq!(handler[0], text_start + 0x54);
+            assert_eq!(handler[2], sp + 32);
+            assert_eq!(handler[11], actions.0 as usize);
+        });
+    }
+}
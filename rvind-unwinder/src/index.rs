@@ -0,0 +1,122 @@
+//! Two-level page index over an unwind table.
+//!
+//! The index starts with an [`IndexHeader`], followed by the page table and
+//! the block table. Text is split into pages of `1 << page_shift` bytes, and
+//! each page into blocks of `1 << block_shift` bytes:
+//!
+//! - The page table has a `u32` for each page, and one more for the end of the
+//!   last page, holding the number of entries whose code offset is at most the
+//!   start of that page.
+//! - The block table has a `u16` for each block of each page, holding the
+//!   number of entries whose code offset is at most the start of that block,
+//!   minus the number for its page.
+//!
+//! The counts for a block and for the next one bound the entries that can
+//! cover an offset in the block, so a lookup only searches those.
+
+use core::ops::Range;
+
+use zerocopy::{AsBytes, FromBytes, LayoutVerified};
+
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct IndexHeader {
+    pub page_shift: u8,
+    pub block_shift: u8,
+    pub reserved: u16,
+    pub page_count: u32,
+}
+
+impl IndexHeader {
+    /// Largest page size, so that block counts always fit in a `u16`
+    pub const MAX_PAGE_SHIFT: u8 = 16;
+
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PageIndex<'a> {
+    page_shift: u8,
+    block_shift: u8,
+    pages: &'a [u32],
+    blocks: &'a [u16],
+}
+
+impl<'a> PageIndex<'a> {
+    /// Parses an index, checking that its counts never decrease.
+    ///
+    /// Returns `None` if `bytes` is misaligned or malformed.
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        let (header, rest) = LayoutVerified::<_, IndexHeader>::new_from_prefix(bytes)?;
+
+        if header.block_shift > header.page_shift || header.page_shift > IndexHeader::MAX_PAGE_SHIFT
+        {
+            return None;
+        }
+
+        let page_count = header.page_count as usize;
+        let block_count =
+            page_count.checked_shl((header.page_shift - header.block_shift) as u32)?;
+
+        let (pages, rest) =
+            LayoutVerified::<_, [u32]>::new_slice_from_prefix(rest, page_count.checked_add(1)?)?;
+        let (blocks, _) = LayoutVerified::<_, [u16]>::new_slice_from_prefix(rest, block_count)?;
+
+        let index = Self {
+            page_shift: header.page_shift,
+            block_shift: header.block_shift,
+            pages: pages.into_slice(),
+            blocks: blocks.into_slice(),
+        };
+
+        if !index.pages.windows(2).all(|w| w[0] <= w[1]) {
+            return None;
+        }
+
+        for (page, blocks) in index.pages.windows(2).zip(index.page_blocks()) {
+            let (start, end) = (page[0] as usize, page[1] as usize);
+
+            if !blocks.windows(2).all(|w| w[0] <= w[1])
+                || blocks.iter().any(|&b| start + b as usize > end)
+            {
+                return None;
+            }
+        }
+
+        Some(index)
+    }
+
+    fn page_blocks(&self) -> impl Iterator<Item = &'a [u16]> {
+        self.blocks
+            .chunks(1 << (self.page_shift - self.block_shift))
+    }
+
+    /// Number of entries the index was built for.
+    pub fn entry_count(&self) -> usize {
+        self.pages.last().map_or(0, |&n| n as usize)
+    }
+
+    /// Range of indices of the entries that can cover `offset`, if `offset`
+    /// is in indexed text.
+    ///
+    /// The last entry whose code offset is at most `offset` is either in the
+    /// range or immediately before it.
+    pub fn range(&self, offset: u32) -> Option<Range<usize>> {
+        let page = (offset >> self.page_shift) as usize;
+        let block = (offset >> self.block_shift) as usize;
+        let blocks_per_page = 1 << (self.page_shift - self.block_shift);
+
+        let page_start = *self.pages.get(page)? as usize;
+        let start = page_start + *self.blocks.get(block)? as usize;
+
+        let end = if (block + 1).is_multiple_of(blocks_per_page) {
+            *self.pages.get(page + 1)? as usize
+        } else {
+            page_start + *self.blocks.get(block + 1)? as usize
+        };
+
+        Some(start..end)
+    }
+}
@@ -1,27 +1,237 @@
 #![no_std]
 #![deny(unsafe_op_in_unsafe_fn)]
+// `UnwindError` carries a whole `CallFrame`, and there is no allocator to box it
+#![allow(clippy::result_large_err)]
 
-use core::{mem::size_of, slice};
+mod capture;
+pub mod compact;
+pub mod container;
+// Host tests get the unwinding logic, without the assembly and symbols
+#[cfg(all(feature = "eh", any(target_arch = "riscv64", test)))]
+pub mod eh;
+pub mod index;
+mod linked;
+mod lsda;
+mod registry;
+mod symbols;
 
-use zerocopy::{AsBytes, FromBytes};
+pub use capture::capture;
+pub use compact::CompactTable;
+pub use container::{Table, TableError};
+pub use index::PageIndex;
+#[doc(hidden)]
+pub use linked::bytes_between;
+pub use lsda::{Lsda, Lsdas};
+pub use registry::{Module, RegisterError, Registry, UnwindTable};
+pub use symbols::{Symbol, SymbolTable};
+
+use core::{fmt, mem::size_of, ops::Range, slice};
+
+use zerocopy::{AsBytes, FromBytes, LayoutVerified};
 
 #[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
 #[repr(C)]
 pub struct Entry {
+    /// Start of the covered code, relative to the start of text
     pub code_offset: u32,
+    /// The caller's sp is `sp_reg` plus `sp_offset`
     pub sp_offset: u32,
+    /// Any register other than sp, fp and `s1` through `s11` is only known in
+    /// the first frame and in frames unwound from a trap frame
     pub sp_reg: u8,
+    /// The caller's fp is saved at this many bytes below the caller's sp, or
+    /// is still in fp if `u8::MAX`
     pub fp_offset: u8,
+    /// Like `fp_offset`, but for the return address and ra
     pub ra_offset: u8,
     pub flag: u8,
 }
 
 impl Entry {
+    /// The entry describes how to unwind; without it the code is not unwindable
+    pub const FLAG_VALID: u8 = 1 << 0;
+    /// `sp_reg` plus `sp_offset` locates a trap frame instead of the caller's sp
+    ///
+    /// A trap frame holds the trapped pc followed by `x1` through `x31`, one
+    /// register-sized slot each, so that slot `i` holds `x{i}`.
+    pub const FLAG_TRAP: u8 = 1 << 1;
+    /// `fp_offset` and `ra_offset` are unused, and the `WideEntry` covering
+    /// the code gives them instead
+    pub const FLAG_WIDE: u8 = 1 << 2;
+    /// The top bits of `sp_offset` from `VLENB_SHIFT` up are a multiple of
+    /// `vlenb` to add to the rest, for frames that spill vector registers
+    pub const FLAG_VLENB: u8 = 1 << 3;
+    /// Some of `s1` through `s11` may no longer hold the caller's values, which
+    /// only an `ExtEntry` says where to find. Without it they all still do.
+    pub const FLAG_SAVED_REGS: u8 = 1 << 4;
+
+    /// Position of the multiple of `vlenb` in `sp_offset`, see `FLAG_VLENB`
+    pub const VLENB_SHIFT: u32 = 24;
+
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+/// Save offsets for entries with `Entry::FLAG_WIDE`, whose frames save fp or
+/// ra too far below the caller's sp for a `u8`.
+///
+/// Wide entries are kept in their own array sorted by code offset, and an
+/// entry uses the last one whose code offset is at most that of the pc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct WideEntry {
+    pub code_offset: u32,
+    /// Like `Entry::fp_offset`, or `WideEntry::IN_REG` if still in fp
+    pub fp_offset: u32,
+    /// Like `Entry::ra_offset`, or `WideEntry::IN_REG` if still in ra
+    pub ra_offset: u32,
+}
+
+impl WideEntry {
+    pub const IN_REG: u32 = u32::MAX;
+
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+
+    /// Returns `None` if `bytes` is misaligned or not a whole number of records.
+    pub fn slice_from_bytes(bytes: &[u8]) -> Option<&[WideEntry]> {
+        Some(LayoutVerified::<_, [WideEntry]>::new_slice(bytes)?.into_slice())
+    }
+
+    /// Finds the last entry in `entries` whose code offset is at most `offset`.
+    pub fn find(entries: &[WideEntry], offset: u32) -> Option<WideEntry> {
+        let index = entries.partition_point(|e| e.code_offset <= offset);
+        entries.get(index.checked_sub(1)?).copied()
+    }
+}
+
+/// Number of callee-saved registers besides fp, `s1` through `s11`
+pub const SAVED_REGS: usize = 11;
+
+/// Register number of each of `s1` through `s11`
+pub const SAVED_REG_NUMBERS: [usize; SAVED_REGS] = [9, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27];
+
+/// An `Entry` that also says where to find the caller's `s1` through `s11`.
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct ExtEntry {
+    pub entry: Entry,
+    /// Like `Entry::fp_offset`, for each of `s1` through `s11`, or
+    /// `ExtEntry::LOST` if the caller's value cannot be recovered
+    pub saved: [u8; SAVED_REGS],
+    pub reserved: u8,
+}
+
+impl ExtEntry {
+    pub const LOST: u8 = u8::MAX - 1;
+
     pub fn to_bytes(&self) -> &[u8] {
         <_ as AsBytes>::as_bytes(self)
     }
 }
 
+impl From<Entry> for ExtEntry {
+    /// An entry that keeps `s1` through `s11` in their registers, or recovers
+    /// none of them if the entry has `Entry::FLAG_SAVED_REGS`.
+    fn from(entry: Entry) -> Self {
+        let saved = if (entry.flag & Entry::FLAG_SAVED_REGS) != 0 {
+            Self::LOST
+        } else {
+            u8::MAX
+        };
+
+        Self {
+            entry,
+            saved: [saved; SAVED_REGS],
+            reserved: 0,
+        }
+    }
+}
+
+/// Unwind entries in any table format.
+#[derive(Debug, Clone, Copy)]
+pub enum Entries<'a> {
+    Fixed(&'a [Entry]),
+    Compact(CompactTable<'a>),
+    Extended(&'a [ExtEntry]),
+}
+
+impl Entries<'_> {
+    /// Finds the last entry whose code offset is at most `offset`.
+    pub fn find(&self, offset: u32) -> Option<ExtEntry> {
+        match self {
+            Entries::Fixed(entries) => {
+                let index = entries.partition_point(|e| e.code_offset <= offset);
+                entries.get(index.checked_sub(1)?).copied().map(Into::into)
+            }
+            Entries::Compact(table) => table.find(offset).map(Into::into),
+            Entries::Extended(entries) => {
+                let index = entries.partition_point(|e| e.entry.code_offset <= offset);
+                entries.get(index.checked_sub(1)?).copied()
+            }
+        }
+    }
+
+    /// Like `find`, but only searches the entries that `index` allows for
+    /// `offset`.
+    ///
+    /// Compact tables have their own block index and are searched as usual,
+    /// as are offsets outside the indexed text.
+    pub fn find_indexed(&self, index: &PageIndex, offset: u32) -> Option<ExtEntry> {
+        let Some(range) = index.range(offset) else {
+            return self.find(offset);
+        };
+        let start = range.start;
+
+        match self {
+            Entries::Fixed(entries) => {
+                let index = start
+                    + entries
+                        .get(range)?
+                        .partition_point(|e| e.code_offset <= offset);
+                entries.get(index.checked_sub(1)?).copied().map(Into::into)
+            }
+            Entries::Compact(table) => table.find(offset).map(Into::into),
+            Entries::Extended(entries) => {
+                let index = start
+                    + entries
+                        .get(range)?
+                        .partition_point(|e| e.entry.code_offset <= offset);
+                entries.get(index.checked_sub(1)?).copied()
+            }
+        }
+    }
+
+    /// Number of entries, or `None` for compact tables.
+    pub fn entry_count(&self) -> Option<usize> {
+        match self {
+            Entries::Fixed(entries) => Some(entries.len()),
+            Entries::Compact(_) => None,
+            Entries::Extended(entries) => Some(entries.len()),
+        }
+    }
+}
+
+impl<'a> From<&'a [Entry]> for Entries<'a> {
+    fn from(entries: &'a [Entry]) -> Self {
+        Entries::Fixed(entries)
+    }
+}
+
+impl<'a> From<CompactTable<'a>> for Entries<'a> {
+    fn from(table: CompactTable<'a>) -> Self {
+        Entries::Compact(table)
+    }
+}
+
+impl<'a> From<&'a [ExtEntry]> for Entries<'a> {
+    fn from(entries: &'a [ExtEntry]) -> Self {
+        Entries::Extended(entries)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
 pub struct Header {
@@ -29,78 +239,909 @@ pub struct Header {
     pub unwind_len: usize,
 }
 
+impl Header {
+    /// A header for the entries between `start` and `end`.
+    pub fn from_bounds(start: *const u8, end: *const u8) -> Self {
+        Self {
+            unwind: start.cast(),
+            unwind_len: (end as usize).wrapping_sub(start as usize),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `unwind` must point to `unwind_len` bytes of valid `Entry` data that
+    /// outlives the returned slice.
+    pub unsafe fn entries<'a>(&self) -> &'a [Entry] {
+        let len = self.unwind_len / size_of::<Entry>();
+        unsafe { slice::from_raw_parts(self.unwind, len) }
+    }
+}
+
+/// Like `Header`, for a table in the extended format.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ExtHeader {
+    pub unwind: *const ExtEntry,
+    pub unwind_len: usize,
+}
+
+impl ExtHeader {
+    /// A header for the entries between `start` and `end`.
+    pub fn from_bounds(start: *const u8, end: *const u8) -> Self {
+        Self {
+            unwind: start.cast(),
+            unwind_len: (end as usize).wrapping_sub(start as usize),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `unwind` must point to `unwind_len` bytes of valid `ExtEntry` data
+    /// that outlives the returned slice.
+    pub unsafe fn entries<'a>(&self) -> &'a [ExtEntry] {
+        let len = self.unwind_len / size_of::<ExtEntry>();
+        unsafe { slice::from_raw_parts(self.unwind, len) }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Context {
     pub text_start: usize,
+    /// Size of `.text` in bytes
+    pub text_len: usize,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// How a frame was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameSource {
+    /// The frame the walk started from
+    Start,
+    /// Unwound from its callee by an unwind table entry
+    Table,
+    /// Restored from the trap frame saved by its callee, which may be running
+    /// on a different stack
+    Trap,
+    /// Unwound from its callee by following the frame record at fp, which may
+    /// be wrong if the callee does not keep one
+    FramePointer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CallFrame {
     pub pc: usize,
     pub sp: usize,
     pub fp: usize,
+    /// Values of `s1` through `s11`, where they could be recovered
+    pub saved: [Option<usize>; SAVED_REGS],
+    pub source: FrameSource,
+}
+
+impl CallFrame {
+    /// Value of the callee-saved register `s{n}`, where `s0` is fp.
+    pub fn s(&self, n: usize) -> Option<usize> {
+        match n {
+            0 => Some(self.fp),
+            n => *self.saved.get(n - 1)?,
+        }
+    }
 }
 
 pub struct FirstFrame {
     pub ra: usize,
     pub frame: CallFrame,
+    /// Every register as the walk starts, laid out like a trap frame, if
+    /// known
+    ///
+    /// Only needed for code whose entries use a register other than sp, fp
+    /// and `s1` through `s11` as the base of the caller's sp. Those registers
+    /// and ra are taken from the other fields.
+    pub regs: Option<[usize; 32]>,
+    /// Whether `frame.pc` is the return address of a call made by the first
+    /// frame, rather than the next instruction to run in it
+    pub return_address: bool,
 }
 
-pub unsafe fn unwind(
-    header: &'static Header,
-    context: &Context,
-    first_frame: FirstFrame,
-    debug: &mut dyn FnMut(CallFrame),
-) {
-    let len = header.unwind_len / size_of::<Entry>();
-    let entries = unsafe { slice::from_raw_parts(header.unwind, len) };
+impl FirstFrame {
+    /// A first frame with the pc in `regs[0]` and `x{i}` in `regs[i]`, the
+    /// layout of a trap frame.
+    pub fn from_regs(regs: [usize; 32]) -> Self {
+        Self {
+            ra: regs[1],
+            frame: CallFrame {
+                pc: regs[0],
+                sp: regs[2],
+                fp: regs[8],
+                saved: SAVED_REG_NUMBERS.map(|n| Some(regs[n])),
+                source: FrameSource::Start,
+            },
+            regs: Some(regs),
+            return_address: false,
+        }
+    }
+}
+
+/// Source of the saved register values the unwinder loads from the stack.
+///
+/// Returning `None` stops the unwind instead of faulting, so implementations
+/// can probe memory safely or serve reads from a captured stack snapshot.
+pub trait MemoryReader {
+    fn read_usize(&self, addr: usize) -> Option<usize>;
 
-    fn load(addr: usize) -> usize {
-        unsafe { (addr as *const usize).read() }
+    /// Size in bytes of each value `read_usize` loads, and of each trap frame
+    /// slot. Only differs from `usize` when reading another target's memory.
+    fn word_size(&self) -> usize {
+        size_of::<usize>()
     }
+}
+
+/// Reads memory by dereferencing raw pointers.
+#[derive(Debug, Clone, Copy)]
+pub struct DirectMemory(());
 
-    (|| -> Option<()> {
-        let mut frame = first_frame.frame;
-        let mut is_top = true;
+impl DirectMemory {
+    /// # Safety
+    ///
+    /// Every address the unwinder asks for must be valid and aligned for a
+    /// `usize` read. Any misaligned address is rejected rather than read.
+    pub unsafe fn new() -> Self {
+        Self(())
+    }
+}
 
-        loop {
-            debug(frame);
+impl MemoryReader for DirectMemory {
+    fn read_usize(&self, addr: usize) -> Option<usize> {
+        if !addr.is_multiple_of(size_of::<usize>()) {
+            return None;
+        }
 
-            let offset: usize = frame.pc - context.text_start;
-            let offset: u32 = offset.try_into().ok()?;
-            let index = entries.partition_point(|e| e.code_offset <= offset);
+        // SAFETY: Guaranteed by the contract of `DirectMemory::new`
+        Some(unsafe { (addr as *const usize).read() })
+    }
+}
 
-            let entry = entries.get(index.checked_sub(1)?)?;
+impl<M: MemoryReader + ?Sized> MemoryReader for &M {
+    fn read_usize(&self, addr: usize) -> Option<usize> {
+        (**self).read_usize(addr)
+    }
+
+    fn word_size(&self) -> usize {
+        (**self).word_size()
+    }
+}
+
+/// Why a frame could not be unwound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnwindErrorKind {
+    /// No registered module covers the pc
+    NoModule,
+    /// The pc is below the start of the module's text
+    PcBeforeText,
+    /// The pc is past the end of the module's text
+    PcAfterText,
+    /// The pc is too far into the module's text for a `u32` code offset
+    OffsetOverflow,
+    /// The pc is before the first entry of the table
+    NoEntry,
+    /// The entry covering the pc is marked as not unwindable
+    NotUnwindable,
+    /// The entry names an unsupported register as the base of the caller's sp
+    BadSpReg(u8),
+    /// The entry names a register whose value is not known for this frame as
+    /// the base of the caller's sp
+    SpRegUnknown(u8),
+    /// The return address is still in ra, but ra is not known for this frame
+    RaNotSaved,
+    /// The entry has `Entry::FLAG_WIDE`, but there is no `WideEntry` for it
+    NoWideEntry,
+    /// The entry has `Entry::FLAG_VLENB`, but the value of `vlenb` is not known
+    VlenbUnknown,
+    /// The memory reader could not load a saved register from `addr`
+    BadRead { addr: usize },
+    /// A saved register would be loaded from outside the stack bounds
+    ReadOutOfBounds { addr: usize },
+    /// The caller's sp is outside the stack bounds
+    SpOutOfBounds { sp: usize },
+    /// The caller's sp is not 16-byte aligned
+    SpMisaligned { sp: usize },
+    /// The caller's sp is not above the current sp
+    SpNotIncreasing { sp: usize },
+    /// The caller's pc is not in any code covered by the unwind table
+    PcOutsideText { pc: usize },
+    /// The maximum number of frames has been reached
+    DepthExceeded,
+}
+
+impl fmt::Display for UnwindErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use UnwindErrorKind::*;
+
+        match self {
+            NoModule => write!(f, "no module covers pc"),
+            PcBeforeText => write!(f, "pc before start of text"),
+            PcAfterText => write!(f, "pc after end of text"),
+            OffsetOverflow => write!(f, "code offset overflows"),
+            NoEntry => write!(f, "no unwind entry"),
+            NotUnwindable => write!(f, "code marked as not unwindable"),
+            BadSpReg(reg) => write!(f, "unsupported sp register x{reg}"),
+            SpRegUnknown(reg) => write!(f, "sp register x{reg} not known"),
+            RaNotSaved => write!(f, "ra not saved in non-top frame"),
+            NoWideEntry => write!(f, "no wide unwind entry"),
+            VlenbUnknown => write!(f, "vlenb not known"),
+            BadRead { addr } => write!(f, "cannot read memory at {addr:#x}"),
+            ReadOutOfBounds { addr } => write!(f, "read at {addr:#x} outside stack"),
+            SpOutOfBounds { sp } => write!(f, "caller sp {sp:#x} outside stack"),
+            SpMisaligned { sp } => write!(f, "caller sp {sp:#x} misaligned"),
+            SpNotIncreasing { sp } => write!(f, "caller sp {sp:#x} not above current sp"),
+            PcOutsideText { pc } => write!(f, "caller pc {pc:#x} outside text"),
+            DepthExceeded => write!(f, "maximum depth exceeded"),
+        }
+    }
+}
+
+/// The frame at which unwinding stopped early, and the reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnwindError {
+    pub frame: CallFrame,
+    pub kind: UnwindErrorKind,
+}
+
+impl fmt::Display for UnwindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at pc {:#x} (sp {:#x}, fp {:#x})",
+            self.kind, self.frame.pc, self.frame.sp, self.frame.fp
+        )
+    }
+}
+
+/// Sanity checks applied to every frame in checked mode.
+///
+/// In addition to the bounds given here, checked mode requires each caller's
+/// sp to be 16-byte aligned and strictly above the callee's, and each caller's
+/// pc to be covered by the unwind table. The sp restored from a trap frame
+/// only has to be aligned and in bounds, as the trap may have switched stacks.
+#[derive(Debug, Clone)]
+pub struct Checks {
+    /// Memory holding the stack, or all stacks if traps switch between them.
+    /// All saved registers are loaded from here.
+    pub stack: Range<usize>,
+    /// Maximum number of frames to yield, including the first one
+    pub max_depth: usize,
+}
+
+/// Value of the `vlenb` CSR on the running hart, if the crate is built with
+/// the V extension.
+fn current_vlenb() -> Option<usize> {
+    #[cfg(all(
+        any(target_arch = "riscv32", target_arch = "riscv64"),
+        target_feature = "v"
+    ))]
+    {
+        let vlenb;
+        // SAFETY: Reading vlenb has no side effects.
+        unsafe {
+            core::arch::asm!("csrr {}, vlenb", out(reg) vlenb, options(nomem, nostack));
+        }
+        Some(vlenb)
+    }
+
+    #[cfg(not(all(
+        any(target_arch = "riscv32", target_arch = "riscv64"),
+        target_feature = "v"
+    )))]
+    {
+        None
+    }
+}
+
+/// A frame found by one step of the walk.
+struct Step {
+    frame: CallFrame,
+    /// Value of ra, if it is known
+    ra: Option<usize>,
+    /// Values of all registers, laid out like a trap frame, if they are known
+    regs: Option<[usize; 32]>,
+}
+
+/// Walks the call stack one frame at a time.
+///
+/// The first item is the frame the walk started from. Iteration ends with
+/// `None` after the outermost frame, whose return address is zero, or after
+/// yielding an `Err` that says why no further frame could be found.
+pub struct Unwinder<'a, T: ?Sized, M> {
+    table: &'a T,
+    memory: M,
+    /// Value of ra in the current frame, if it is known
+    ra: Option<usize>,
+    /// Values of all registers in the current frame, if they are known
+    regs: Option<[usize; 32]>,
+    frame: CallFrame,
+    /// Whether the pc of the current frame is a return address
+    return_address: bool,
+    checks: Option<Checks>,
+    fp_fallback: bool,
+    /// Value of the `vlenb` CSR, if it is known
+    vlenb: Option<usize>,
+    depth: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T: UnwindTable + ?Sized, M: MemoryReader> Unwinder<'a, T, M> {
+    pub fn new(table: &'a T, first_frame: FirstFrame, memory: M) -> Self {
+        Self {
+            table,
+            memory,
+            ra: Some(first_frame.ra),
+            regs: first_frame.regs,
+            frame: first_frame.frame,
+            return_address: first_frame.return_address,
+            checks: None,
+            fp_fallback: false,
+            vlenb: current_vlenb(),
+            depth: 0,
+            started: false,
+            done: false,
+        }
+    }
 
-            if (entry.flag & 1) == 0 {
-                break None;
+    /// Enables checked mode, so that a corrupted stack ends the walk with an
+    /// error instead of reading wild memory or looping forever.
+    pub fn checked(mut self, checks: Checks) -> Self {
+        self.checks = Some(checks);
+        self
+    }
+
+    /// Follows the frame pointer chain through code that has no unwind entry
+    /// or is marked as not unwindable, such as hand-written assembly.
+    ///
+    /// This assumes the standard frame record, with fp pointing to the
+    /// caller's sp, the return address saved one word below it, and the
+    /// caller's fp one word further down. Frames found this way have
+    /// `FrameSource::FramePointer`, and none of `s1` through `s11`.
+    pub fn frame_pointer_fallback(mut self) -> Self {
+        self.fp_fallback = true;
+        self
+    }
+
+    /// Sets the value of the `vlenb` CSR, for frames sized by it.
+    ///
+    /// It is read from the running hart by default if the crate is built with
+    /// the V extension, and otherwise such frames cannot be unwound without
+    /// this.
+    pub fn vlenb(mut self, vlenb: usize) -> Self {
+        self.vlenb = Some(vlenb);
+        self
+    }
+
+    fn error(&self, kind: UnwindErrorKind) -> UnwindError {
+        UnwindError {
+            frame: self.frame,
+            kind,
+        }
+    }
+
+    fn load(&self, addr: usize) -> Result<usize, UnwindError> {
+        if let Some(checks) = &self.checks {
+            let end = addr.checked_add(self.memory.word_size());
+
+            if addr < checks.stack.start || end.is_none_or(|end| end > checks.stack.end) {
+                return Err(self.error(UnwindErrorKind::ReadOutOfBounds { addr }));
             }
+        }
 
-            let sp_base = match entry.sp_reg {
-                2 => frame.sp,
-                8 => frame.fp,
-                _ => break None,
-            };
+        self.memory
+            .read_usize(addr)
+            .ok_or(self.error(UnwindErrorKind::BadRead { addr }))
+    }
+
+    fn check_sp(&self, frame: &CallFrame) -> Result<(), UnwindError> {
+        use UnwindErrorKind::*;
+
+        let sp = frame.sp;
+
+        if let Some(checks) = &self.checks {
+            if !sp.is_multiple_of(16) {
+                return Err(self.error(SpMisaligned { sp }));
+            }
+
+            if frame.source != FrameSource::Trap && sp <= self.frame.sp {
+                return Err(self.error(SpNotIncreasing { sp }));
+            }
+
+            // The outermost frame may have its sp right at the end of the stack
+            if sp < checks.stack.start || sp > checks.stack.end {
+                return Err(self.error(SpOutOfBounds { sp }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Address at which to look up the entry for the current frame.
+    ///
+    /// A call may be the last instruction of its function, as when calling a
+    /// function that does not return, so a return address is looked up one
+    /// byte back, in the call itself.
+    fn lookup_pc(&self) -> usize {
+        if self.return_address {
+            self.frame.pc.wrapping_sub(1)
+        } else {
+            self.frame.pc
+        }
+    }
+
+    /// Value of register `x{reg}` in the current frame.
+    fn reg(&self, reg: u8) -> Result<usize, UnwindError> {
+        use UnwindErrorKind::*;
+
+        let value = match reg as usize {
+            1 => self.ra,
+            2 => Some(self.frame.sp),
+            8 => Some(self.frame.fp),
+            n @ 3..=31 => match SAVED_REG_NUMBERS.iter().position(|&r| r == n) {
+                Some(i) => self.frame.saved[i],
+                None => self.regs.map(|regs| regs[n]),
+            },
+            _ => return Err(self.error(BadSpReg(reg))),
+        };
+
+        value.ok_or(self.error(SpRegUnknown(reg)))
+    }
+
+    fn step(&self) -> Result<Option<Step>, UnwindError> {
+        use UnwindErrorKind::*;
+
+        let mut frame = self.frame;
+        frame.source = FrameSource::Table;
+
+        let entry = match self.table.find_entry(self.lookup_pc()) {
+            Ok(ext) if (ext.entry.flag & Entry::FLAG_VALID) != 0 => Ok(ext),
+            Ok(_) => Err(NotUnwindable),
+            Err(kind) => Err(kind),
+        };
 
-            frame.sp = sp_base.wrapping_add(entry.sp_offset as usize);
+        let ExtEntry { entry, saved, .. } = match entry {
+            Ok(ext) => ext,
+            Err(NoEntry | NotUnwindable) if self.fp_fallback => return self.step_frame_pointer(),
+            Err(kind) => return Err(self.error(kind)),
+        };
 
-            frame.fp = match entry.fp_offset {
-                u8::MAX => frame.fp,
-                off => load(frame.sp.wrapping_sub(off as usize)),
+        let sp_base = self.reg(entry.sp_reg)?;
+
+        let sp_offset = if (entry.flag & Entry::FLAG_VLENB) != 0 {
+            let vlenb = self.vlenb.ok_or(self.error(VlenbUnknown))?;
+            let scale = (entry.sp_offset >> Entry::VLENB_SHIFT) as usize;
+            let offset = (entry.sp_offset & ((1 << Entry::VLENB_SHIFT) - 1)) as usize;
+            offset.wrapping_add(scale.wrapping_mul(vlenb))
+        } else {
+            entry.sp_offset as usize
+        };
+
+        let mut ra = None;
+        let mut regs = None;
+
+        if (entry.flag & Entry::FLAG_TRAP) != 0 {
+            let trap_frame = sp_base.wrapping_add(sp_offset);
+            let word_size = self.memory.word_size();
+            let mut slots = [0; 32];
+
+            for (i, slot) in slots.iter_mut().enumerate() {
+                *slot = self.load(trap_frame.wrapping_add(i * word_size))?;
+            }
+
+            // The interrupted code may not have saved ra yet, so treat it like
+            // a top frame with all registers restored from the trap frame.
+            frame.source = FrameSource::Trap;
+            frame.pc = slots[0];
+            ra = Some(slots[1]);
+            frame.sp = slots[2];
+            frame.fp = slots[8];
+            self.check_sp(&frame)?;
+
+            for (value, reg) in frame.saved.iter_mut().zip(SAVED_REG_NUMBERS) {
+                *value = Some(slots[reg]);
+            }
+
+            regs = Some(slots);
+        } else {
+            frame.sp = sp_base.wrapping_add(sp_offset);
+            self.check_sp(&frame)?;
+
+            let (fp_offset, ra_offset) = if (entry.flag & Entry::FLAG_WIDE) != 0 {
+                let wide = self
+                    .table
+                    .find_wide(self.lookup_pc())
+                    .ok_or(self.error(NoWideEntry))?;
+                (wide.fp_offset, wide.ra_offset)
+            } else {
+                let widen = |off| match off {
+                    u8::MAX => WideEntry::IN_REG,
+                    off => off as u32,
+                };
+                (widen(entry.fp_offset), widen(entry.ra_offset))
+            };
+
+            frame.fp = match fp_offset {
+                WideEntry::IN_REG => frame.fp,
+                off => self.load(frame.sp.wrapping_sub(off as usize))?,
             };
 
-            frame.pc = match entry.ra_offset {
-                u8::MAX => {
-                    if is_top {
-                        first_frame.ra
-                    } else {
-                        break None;
-                    }
-                }
-                off => load(frame.sp.wrapping_sub(off as usize)),
+            frame.pc = match ra_offset {
+                WideEntry::IN_REG => self.ra.ok_or(self.error(RaNotSaved))?,
+                off => self.load(frame.sp.wrapping_sub(off as usize))?,
             };
 
-            is_top = false;
+            for (value, off) in frame.saved.iter_mut().zip(saved) {
+                *value = match off {
+                    u8::MAX => *value,
+                    ExtEntry::LOST => None,
+                    off => Some(self.load(frame.sp.wrapping_sub(off as usize))?),
+                };
+            }
         }
-    })();
+
+        self.finish(Step { frame, ra, regs })
+    }
+
+    fn step_frame_pointer(&self) -> Result<Option<Step>, UnwindError> {
+        let mut frame = self.frame;
+        frame.source = FrameSource::FramePointer;
+        frame.saved = [None; SAVED_REGS];
+
+        if frame.fp == 0 {
+            // No frame record, as in the outermost frame
+            return Ok(None);
+        }
+
+        let word_size = self.memory.word_size();
+
+        frame.sp = frame.fp;
+        self.check_sp(&frame)?;
+        frame.pc = self.load(frame.sp.wrapping_sub(word_size))?;
+        frame.fp = self.load(frame.sp.wrapping_sub(2 * word_size))?;
+
+        self.finish(Step {
+            frame,
+            ra: None,
+            regs: None,
+        })
+    }
+
+    fn finish(&self, step: Step) -> Result<Option<Step>, UnwindError> {
+        let frame = step.frame;
+
+        if frame.pc == 0 {
+            // Outermost frame reached
+            return Ok(None);
+        }
+
+        // Only a pc restored from a trap frame is not a return address
+        let pc = match frame.source {
+            FrameSource::Trap => frame.pc,
+            _ => frame.pc.wrapping_sub(1),
+        };
+
+        if self.checks.is_some() && !self.table.covers(pc) {
+            return Err(self.error(UnwindErrorKind::PcOutsideText { pc: frame.pc }));
+        }
+
+        Ok(Some(step))
+    }
+}
+
+impl<'a, T: UnwindTable + ?Sized, M: MemoryReader> Iterator for Unwinder<'a, T, M> {
+    type Item = Result<CallFrame, UnwindError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            self.depth = 1;
+            return Some(Ok(self.frame));
+        }
+
+        if let Some(checks) = &self.checks {
+            if self.depth >= checks.max_depth {
+                self.done = true;
+                return Some(Err(self.error(UnwindErrorKind::DepthExceeded)));
+            }
+        }
+
+        match self.step() {
+            Ok(Some(step)) => {
+                self.frame = step.frame;
+                self.return_address = step.frame.source != FrameSource::Trap;
+                self.ra = step.ra;
+                self.regs = step.regs;
+                self.depth += 1;
+                Some(Ok(step.frame))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// # Safety
+///
+/// `header` must describe a valid array of `Entry` that outlives the call.
+pub unsafe fn unwind<M: MemoryReader>(
+    header: &Header,
+    context: &Context,
+    first_frame: FirstFrame,
+    memory: &M,
+    debug: &mut dyn FnMut(CallFrame),
+) -> Result<(), UnwindError> {
+    let module = Module::new(context, unsafe { header.entries() });
+
+    for frame in Unwinder::new(&module, first_frame, memory) {
+        debug(frame?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+
+    const TEXT: usize = 0x8000;
+    const STACK: usize = 0x1000;
+
+    struct Stack<'a>(&'a [usize]);
+
+    impl MemoryReader for Stack<'_> {
+        fn read_usize(&self, addr: usize) -> Option<usize> {
+            self.0
+                .get(addr.checked_sub(STACK)? / size_of::<usize>())
+                .copied()
+        }
+    }
+
+    fn entry(code_offset: u32, flag: u8) -> Entry {
+        Entry {
+            code_offset,
+            sp_offset: 2 * size_of::<usize>() as u32,
+            sp_reg: 2,
+            fp_offset: u8::MAX,
+            ra_offset: size_of::<usize>() as u8,
+            flag: Entry::FLAG_VALID | flag,
+        }
+    }
+
+    /// Walks `stack` in checked mode from `pc`, which is also in ra, with sp
+    /// at `sp`. Returns the number of frames found and the error that ended
+    /// the walk, if any.
+    fn checked_walk(
+        entries: &[Entry],
+        stack: &[usize],
+        pc: usize,
+        sp: usize,
+        checks: Checks,
+    ) -> (usize, Option<UnwindErrorKind>) {
+        let context = Context {
+            text_start: TEXT,
+            text_len: 0x100,
+        };
+        let module = Module::new(&context, entries);
+
+        let mut regs = [0; 32];
+        regs[0] = pc;
+        regs[1] = pc;
+        regs[2] = sp;
+
+        let frames: Vec<_> = Unwinder::new(&module, FirstFrame::from_regs(regs), Stack(stack))
+            .checked(checks)
+            .collect();
+        let error = frames.iter().find_map(|f| f.err()).map(|e| e.kind);
+
+        (frames.iter().filter(|f| f.is_ok()).count(), error)
+    }
+
+    #[test]
+    fn checked_mode_stops_bad_frames() {
+        use UnwindErrorKind::*;
+
+        let word = size_of::<usize>();
+        // 16-byte frames saving ra, and a leaf function at 0x10
+        let frame = Entry {
+            sp_offset: 16,
+            ..entry(0, 0)
+        };
+        let leaf = Entry {
+            code_offset: 0x10,
+            sp_offset: 0,
+            ra_offset: u8::MAX,
+            ..frame
+        };
+        let entries = [frame, leaf];
+        let stack = |ra: usize| {
+            let mut stack = [0; 8];
+            stack[16 / word - 1] = ra;
+            stack[32 / word - 1] = TEXT + 4;
+            stack
+        };
+        let walk = |ra, pc, sp, stack_bounds, max_depth| {
+            let checks = Checks {
+                stack: stack_bounds,
+                max_depth,
+            };
+            checked_walk(&entries, &stack(ra), pc, sp, checks)
+        };
+        let bounds = || STACK..STACK + 0x100;
+
+        // Three frames, the last one returning to 0
+        assert_eq!(walk(TEXT + 4, TEXT + 4, STACK, bounds(), 8), (3, None));
+        assert_eq!(
+            walk(TEXT + 4, TEXT + 4, STACK, bounds(), 2),
+            (2, Some(DepthExceeded))
+        );
+        assert_eq!(
+            walk(TEXT + 4, TEXT + 4, STACK + 8, bounds(), 8),
+            (1, Some(SpMisaligned { sp: STACK + 24 }))
+        );
+        assert_eq!(
+            walk(TEXT + 0x200, TEXT + 4, STACK, bounds(), 8),
+            (1, Some(PcOutsideText { pc: TEXT + 0x200 }))
+        );
+        assert_eq!(
+            walk(TEXT + 4, TEXT + 4, STACK, STACK..STACK + 8, 8),
+            (1, Some(SpOutOfBounds { sp: STACK + 16 }))
+        );
+        let ra_slot = STACK + 16 - word;
+        assert_eq!(
+            walk(TEXT + 4, TEXT + 4, STACK, STACK + 16..STACK + 0x100, 8),
+            (1, Some(ReadOutOfBounds { addr: ra_slot }))
+        );
+        // A leaf function whose return address is itself leaves sp in place
+        assert_eq!(
+            walk(0, TEXT + 0x14, STACK, bounds(), 8),
+            (1, Some(SpNotIncreasing { sp: STACK }))
+        );
+    }
+
+    #[test]
+    fn fixed_entries_keep_unsaved_regs() {
+        let entries = [entry(0, 0), entry(0x10, Entry::FLAG_SAVED_REGS)];
+        let context = Context {
+            text_start: TEXT,
+            text_len: 0x100,
+        };
+        let module = Module::new(&context, &entries[..]);
+        let stack = [0, TEXT + 0x14, 0, TEXT + 4, 0, 0];
+
+        let mut regs = [0; 32];
+        regs[0] = TEXT + 4;
+        regs[2] = STACK;
+        regs[9] = 42;
+
+        let s1: Vec<Option<usize>> =
+            Unwinder::new(&module, FirstFrame::from_regs(regs), Stack(&stack))
+                .map(|frame| frame.unwrap().s(1))
+                .collect();
+
+        assert_eq!(s1, [Some(42), Some(42), None]);
+    }
+
+    #[test]
+    fn return_address_past_end_of_function() {
+        // A call at the end of one function, followed by a leaf function
+        let leaf = Entry {
+            code_offset: 0x10,
+            sp_offset: 0,
+            sp_reg: 2,
+            fp_offset: u8::MAX,
+            ra_offset: u8::MAX,
+            flag: Entry::FLAG_VALID,
+        };
+        let entries = [entry(0, 0), leaf];
+        let context = Context {
+            text_start: TEXT,
+            text_len: 0x100,
+        };
+        let module = Module::new(&context, &entries[..]);
+        let stack = [0, TEXT + 0x10, 0, 0];
+
+        let mut regs = [0; 32];
+        regs[0] = TEXT + 4;
+        regs[2] = STACK;
+
+        let pcs: Vec<usize> = Unwinder::new(&module, FirstFrame::from_regs(regs), Stack(&stack))
+            .map(|frame| frame.unwrap().pc)
+            .collect();
+
+        assert_eq!(pcs, [TEXT + 4, TEXT + 0x10]);
+    }
+
+    #[test]
+    fn vlenb_sized_frames() {
+        let word = size_of::<usize>();
+        // 16 bytes plus one vector register
+        let entries = [Entry {
+            sp_offset: 16 | (1 << Entry::VLENB_SHIFT),
+            ..entry(0, Entry::FLAG_VLENB)
+        }];
+        let context = Context {
+            text_start: TEXT,
+            text_len: 0x100,
+        };
+        let module = Module::new(&context, &entries[..]);
+        let mut stack = [0; 16];
+        stack[48 / word - 1] = TEXT + 8;
+
+        let mut regs = [0; 32];
+        regs[0] = TEXT + 4;
+        regs[2] = STACK;
+
+        let frames: Vec<_> = Unwinder::new(&module, FirstFrame::from_regs(regs), Stack(&stack))
+            .vlenb(32)
+            .map(|frame| frame.map(|f| (f.pc, f.sp)))
+            .collect();
+
+        assert_eq!(frames, [Ok((TEXT + 4, STACK)), Ok((TEXT + 8, STACK + 48))]);
+
+        let error = Unwinder::new(&module, FirstFrame::from_regs(regs), Stack(&stack))
+            .find_map(|frame| frame.err());
+
+        assert_eq!(error.map(|e| e.kind), Some(UnwindErrorKind::VlenbUnknown));
+    }
+
+    #[test]
+    fn frame_pointer_through_gap() {
+        let word = size_of::<usize>();
+        // Code with a frame, then code with no usable entry
+        let gap = Entry {
+            flag: 0,
+            ..entry(0x10, 0)
+        };
+        let entries = [entry(0, 0), gap];
+        let context = Context {
+            text_start: TEXT,
+            text_len: 0x100,
+        };
+        let module = Module::new(&context, &entries[..]);
+        // A frame record at 0x20, returning to the first function
+        let fp = STACK + 0x20;
+        let mut stack = [0; 8];
+        stack[0x20 / word - 1] = TEXT + 4;
+
+        let mut regs = [0; 32];
+        regs[0] = TEXT + 0x14;
+        regs[2] = STACK;
+        regs[8] = fp;
+
+        let frames: Vec<_> = Unwinder::new(&module, FirstFrame::from_regs(regs), Stack(&stack))
+            .frame_pointer_fallback()
+            .map(|frame| frame.map(|f| (f.pc, f.sp, f.source)))
+            .collect();
+
+        assert_eq!(
+            frames,
+            [
+                Ok((TEXT + 0x14, STACK, FrameSource::Start)),
+                Ok((TEXT + 4, fp, FrameSource::FramePointer)),
+            ]
+        );
+
+        let error = Unwinder::new(&module, FirstFrame::from_regs(regs), Stack(&stack))
+            .find_map(|frame| frame.err());
+
+        assert_eq!(error.map(|e| e.kind), Some(UnwindErrorKind::NotUnwindable));
+    }
 }
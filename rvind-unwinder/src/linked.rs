@@ -0,0 +1,154 @@
+//! Access to the tables that `rvind-analyze --object` links into the image.
+
+/// # Safety
+///
+/// `start..end` must be valid for reads for `'static`.
+pub unsafe fn bytes_between(start: *const u8, end: *const u8) -> &'static [u8] {
+    let len = (end as usize).wrapping_sub(start as usize);
+    unsafe { core::slice::from_raw_parts(start, len) }
+}
+
+/// Builds a `Header` for the linked `.rvind_unwind` section, or an
+/// `ExtHeader` with `linked_header!(extended)` if it was written with
+/// `--extended`.
+#[macro_export]
+macro_rules! linked_header {
+    () => {
+        $crate::linked_header!(@bounds $crate::Header)
+    };
+    (extended) => {
+        $crate::linked_header!(@bounds $crate::ExtHeader)
+    };
+    (@bounds $header:path) => {{
+        extern "C" {
+            static __rvind_unwind_start: u8;
+            static __rvind_unwind_end: u8;
+        }
+
+        // SAFETY: Only the addresses of the symbols are taken
+        #[allow(unused_unsafe)]
+        let (start, end) = unsafe {
+            (
+                ::core::ptr::addr_of!(__rvind_unwind_start),
+                ::core::ptr::addr_of!(__rvind_unwind_end),
+            )
+        };
+
+        <$header>::from_bounds(start, end)
+    }};
+}
+
+/// Builds a `SymbolTable` for the linked `.rvind_sym` and `.rvind_str`
+/// sections, or `None` if they are misaligned.
+#[macro_export]
+macro_rules! linked_symbols {
+    () => {{
+        extern "C" {
+            static __rvind_sym_start: u8;
+            static __rvind_sym_end: u8;
+            static __rvind_str_start: u8;
+            static __rvind_str_end: u8;
+        }
+
+        // SAFETY: The linker places the whole sections between the symbols
+        let (sym, strings) = unsafe {
+            (
+                $crate::bytes_between(
+                    ::core::ptr::addr_of!(__rvind_sym_start),
+                    ::core::ptr::addr_of!(__rvind_sym_end),
+                ),
+                $crate::bytes_between(
+                    ::core::ptr::addr_of!(__rvind_str_start),
+                    ::core::ptr::addr_of!(__rvind_str_end),
+                ),
+            )
+        };
+
+        $crate::SymbolTable::from_bytes(sym, strings)
+    }};
+}
+
+/// Builds a `Context` from the bounds of `.text` that the linker wrote into
+/// `.rvind_bounds`.
+#[macro_export]
+macro_rules! linked_context {
+    () => {{
+        extern "C" {
+            static __rvind_bounds_start: [usize; 2];
+        }
+
+        // SAFETY: The section holds the start and end of `.text`
+        let [start, end] = unsafe { __rvind_bounds_start };
+
+        $crate::Context {
+            text_start: start,
+            text_len: end.wrapping_sub(start),
+        }
+    }};
+}
+
+/// Builds `Lsdas` for the linked `.rvind_lsda` section, or `None` if it is
+/// misaligned.
+#[macro_export]
+macro_rules! linked_lsdas {
+    () => {{
+        extern "C" {
+            static __rvind_lsda_start: u8;
+            static __rvind_lsda_end: u8;
+        }
+
+        // SAFETY: The linker places the whole section between the symbols
+        let lsdas = unsafe {
+            $crate::bytes_between(
+                ::core::ptr::addr_of!(__rvind_lsda_start),
+                ::core::ptr::addr_of!(__rvind_lsda_end),
+            )
+        };
+
+        $crate::Lsdas::from_bytes(lsdas)
+    }};
+}
+
+/// Builds a `PageIndex` for the linked `.rvind_index` section, or `None` if it
+/// is misaligned or malformed.
+#[macro_export]
+macro_rules! linked_page_index {
+    () => {{
+        extern "C" {
+            static __rvind_index_start: u8;
+            static __rvind_index_end: u8;
+        }
+
+        // SAFETY: The linker places the whole section between the symbols
+        let index = unsafe {
+            $crate::bytes_between(
+                ::core::ptr::addr_of!(__rvind_index_start),
+                ::core::ptr::addr_of!(__rvind_index_end),
+            )
+        };
+
+        $crate::PageIndex::from_bytes(index)
+    }};
+}
+
+/// Gets the `WideEntry` records in the linked `.rvind_wide` section, or `None`
+/// if it is misaligned.
+#[macro_export]
+macro_rules! linked_wide_entries {
+    () => {{
+        extern "C" {
+            static __rvind_wide_start: u8;
+            static __rvind_wide_end: u8;
+        }
+
+        // SAFETY: The linker places the whole section between the symbols
+        let wide = unsafe {
+            $crate::bytes_between(
+                ::core::ptr::addr_of!(__rvind_wide_start),
+                ::core::ptr::addr_of!(__rvind_wide_end),
+            )
+        };
+
+        $crate::WideEntry::slice_from_bytes(wide)
+    }};
+}
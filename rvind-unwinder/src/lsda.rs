@@ -0,0 +1,51 @@
+use zerocopy::{AsBytes, FromBytes, LayoutVerified};
+
+/// A function that has an LSDA, taken from the FDE describing it.
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct Lsda {
+    /// Start of the function, relative to the start of text
+    pub code_offset: u32,
+    /// Size of the function in bytes
+    pub code_len: u32,
+    /// Address of the LSDA relative to the start of text, as a signed value
+    pub lsda: u32,
+    pub reserved: u32,
+}
+
+impl Lsda {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+
+    /// Address of the LSDA for code with text starting at `text_start`.
+    pub fn address(&self, text_start: usize) -> usize {
+        text_start.wrapping_add(self.lsda as i32 as isize as usize)
+    }
+}
+
+/// Maps code offsets to the LSDAs of the functions covering them.
+#[derive(Debug, Clone, Copy)]
+pub struct Lsdas<'a> {
+    lsdas: &'a [Lsda],
+}
+
+impl<'a> Lsdas<'a> {
+    /// `lsdas` must be sorted by `code_offset` and must not overlap.
+    pub fn new(lsdas: &'a [Lsda]) -> Self {
+        Self { lsdas }
+    }
+
+    /// Returns `None` if `bytes` is misaligned or not a whole number of records.
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        let lsdas = LayoutVerified::<_, [Lsda]>::new_slice(bytes)?.into_slice();
+        Some(Self::new(lsdas))
+    }
+
+    /// Finds the function containing `offset`, if it has an LSDA.
+    pub fn find(&self, offset: u32) -> Option<&'a Lsda> {
+        let index = self.lsdas.partition_point(|l| l.code_offset <= offset);
+        let lsda = self.lsdas.get(index.checked_sub(1)?)?;
+        (offset - lsda.code_offset < lsda.code_len).then_some(lsda)
+    }
+}
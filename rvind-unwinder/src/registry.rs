@@ -0,0 +1,379 @@
+use core::{
+    fmt, ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+use crate::{Context, Entries, ExtEntry, PageIndex, UnwindErrorKind, WideEntry};
+
+/// Source of unwind entries consulted for each frame.
+pub trait UnwindTable {
+    fn find_entry(&self, pc: usize) -> Result<ExtEntry, UnwindErrorKind>;
+
+    /// Whether `pc` is in code described by this table.
+    fn covers(&self, pc: usize) -> bool;
+
+    /// Finds the save offsets for an entry with `Entry::FLAG_WIDE`.
+    fn find_wide(&self, pc: usize) -> Option<WideEntry> {
+        let _ = pc;
+        None
+    }
+}
+
+/// An unwind table together with the code it describes.
+#[derive(Debug, Clone, Copy)]
+pub struct Module<'a> {
+    /// Link-time address that the code offsets in `entries` are relative to
+    pub text_start: usize,
+    /// Size of the described code in bytes
+    pub text_len: usize,
+    /// Difference between the run-time and link-time addresses of the code
+    pub load_bias: usize,
+    pub entries: Entries<'a>,
+    /// Index built for `entries` to speed up lookups
+    pub index: Option<PageIndex<'a>>,
+    /// Save offsets for the entries with `Entry::FLAG_WIDE`
+    pub wide: &'a [WideEntry],
+}
+
+impl<'a> Module<'a> {
+    /// A module covering the `.text` described by `context`.
+    pub fn new(context: &Context, entries: impl Into<Entries<'a>>) -> Self {
+        Self {
+            text_start: context.text_start,
+            text_len: context.text_len,
+            load_bias: 0,
+            entries: entries.into(),
+            index: None,
+            wide: &[],
+        }
+    }
+
+    pub(crate) fn code_offset(&self, pc: usize) -> Option<usize> {
+        pc.wrapping_sub(self.load_bias).checked_sub(self.text_start)
+    }
+
+    pub fn contains(&self, pc: usize) -> bool {
+        matches!(self.code_offset(pc), Some(off) if off < self.text_len)
+    }
+}
+
+impl UnwindTable for Module<'_> {
+    fn find_entry(&self, pc: usize) -> Result<ExtEntry, UnwindErrorKind> {
+        use UnwindErrorKind::*;
+
+        let offset = self.code_offset(pc).ok_or(PcBeforeText)?;
+
+        if offset >= self.text_len {
+            return Err(PcAfterText);
+        }
+
+        let offset: u32 = offset.try_into().map_err(|_| OffsetOverflow)?;
+
+        match &self.index {
+            Some(index) => self.entries.find_indexed(index, offset),
+            None => self.entries.find(offset),
+        }
+        .ok_or(NoEntry)
+    }
+
+    fn covers(&self, pc: usize) -> bool {
+        self.contains(pc)
+    }
+
+    fn find_wide(&self, pc: usize) -> Option<WideEntry> {
+        let offset = self.code_offset(pc)?.try_into().ok()?;
+        WideEntry::find(self.wide, offset)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterError {
+    /// Every slot is taken
+    Full,
+    /// The module is already registered
+    AlreadyRegistered,
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegisterError::Full => write!(f, "registry full"),
+            RegisterError::AlreadyRegistered => write!(f, "module already registered"),
+        }
+    }
+}
+
+/// Counts a lookup as in progress until dropped, even if the lookup panics.
+struct Reading<'a> {
+    readers: &'a AtomicUsize,
+}
+
+impl Drop for Reading<'_> {
+    fn drop(&mut self) {
+        self.readers.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A fixed-capacity set of modules that can be updated without locking.
+///
+/// Registration and removal may race with each other and with unwinders
+/// reading the registry. Once `unregister` returns, the module is no longer
+/// used and its code and table can be unmapped. To know when that is, each
+/// lookup counts itself in the current epoch, and `unregister` starts a new
+/// epoch and waits for the lookups of the old one to finish. Lookups that
+/// start later cannot see the module, so the wait ends even while other harts
+/// keep unwinding.
+///
+/// As `unregister` waits, it must not be called where it can interrupt a
+/// lookup or another `unregister` on the same hart, such as from an interrupt
+/// handler while a thread may be unwinding.
+pub struct Registry<const N: usize> {
+    slots: [AtomicPtr<Module<'static>>; N],
+    /// Index into `readers` for the lookups starting now
+    epoch: AtomicUsize,
+    /// Number of lookups in progress that started in each epoch
+    readers: [AtomicUsize; 2],
+    /// Held by the `unregister` waiting for an epoch to end
+    waiting: AtomicBool,
+}
+
+impl<const N: usize> Registry<N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { AtomicPtr::new(ptr::null_mut()) }; N],
+            epoch: AtomicUsize::new(0),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            waiting: AtomicBool::new(false),
+        }
+    }
+
+    pub fn register(&self, module: &'static Module<'static>) -> Result<(), RegisterError> {
+        // SAFETY: The module is never freed
+        unsafe { self.register_scoped(module) }
+    }
+
+    /// Like `register`, for a module that only lives until it is unregistered,
+    /// such as one for a loadable module or an overlay.
+    ///
+    /// # Safety
+    ///
+    /// `module`, and the table and code it describes, must stay valid until
+    /// `unregister` for it has returned.
+    pub unsafe fn register_scoped(&self, module: &Module<'_>) -> Result<(), RegisterError> {
+        let new = module as *const Module as *mut Module<'static>;
+
+        // Registering the same module from two threads at once is not caught
+        if self
+            .slots
+            .iter()
+            .any(|slot| slot.load(Ordering::SeqCst) == new)
+        {
+            return Err(RegisterError::AlreadyRegistered);
+        }
+
+        for slot in &self.slots {
+            if slot
+                .compare_exchange(ptr::null_mut(), new, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Ok(());
+            }
+        }
+
+        Err(RegisterError::Full)
+    }
+
+    /// Removes `module` and waits until no lookup is using it.
+    ///
+    /// Returns `false` if `module` was not registered.
+    pub fn unregister(&self, module: &Module<'_>) -> bool {
+        let old = module as *const Module as *mut Module<'static>;
+
+        if !self.slots.iter().any(|slot| {
+            slot.compare_exchange(old, ptr::null_mut(), Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+        }) {
+            return false;
+        }
+
+        // Two removals waiting at once could each start an epoch that the
+        // other is waiting for
+        while self
+            .waiting
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+
+        // Lookups that may have seen the module have counted themselves in
+        // the old epoch, and those counting themselves in it from now on load
+        // the slot too late to see it
+        let epoch = self.epoch.fetch_xor(1, Ordering::SeqCst);
+
+        while self.readers[epoch].load(Ordering::SeqCst) != 0 {
+            core::hint::spin_loop();
+        }
+
+        self.waiting.store(false, Ordering::Release);
+        true
+    }
+
+    fn reading(&self) -> Reading<'_> {
+        let readers = &self.readers[self.epoch.load(Ordering::SeqCst)];
+        readers.fetch_add(1, Ordering::SeqCst);
+        Reading { readers }
+    }
+
+    /// Calls `f` with the module covering `pc`, which stays registered until
+    /// `f` returns.
+    pub fn with_module<R>(&self, pc: usize, f: impl FnOnce(&Module<'_>) -> R) -> Option<R> {
+        let _reading = self.reading();
+
+        for slot in &self.slots {
+            // SAFETY: The module stays valid while it is in the slot, and
+            // `unregister` waits for this lookup before returning
+            let module = unsafe { slot.load(Ordering::SeqCst).as_ref() };
+
+            if let Some(module) = module.filter(|m| m.contains(pc)) {
+                return Some(f(module));
+            }
+        }
+
+        None
+    }
+}
+
+impl<const N: usize> Default for Registry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> UnwindTable for Registry<N> {
+    fn find_entry(&self, pc: usize) -> Result<ExtEntry, UnwindErrorKind> {
+        self.with_module(pc, |module| module.find_entry(pc))
+            .ok_or(UnwindErrorKind::NoModule)?
+    }
+
+    fn covers(&self, pc: usize) -> bool {
+        self.with_module(pc, |_| ()).is_some()
+    }
+
+    fn find_wide(&self, pc: usize) -> Option<WideEntry> {
+        self.with_module(pc, |module| module.find_wide(pc))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{panic, thread, vec::Vec};
+
+    use super::*;
+    use crate::Entry;
+
+    fn module(text_start: usize) -> Module<'static> {
+        let context = Context {
+            text_start,
+            text_len: 0x100,
+        };
+
+        Module::new(&context, &[][..] as &[Entry])
+    }
+
+    fn text_start(registry: &Registry<2>, pc: usize) -> Option<usize> {
+        registry.with_module(pc, |module| module.text_start)
+    }
+
+    #[test]
+    fn register_and_unregister() {
+        let registry = Registry::<2>::new();
+        let modules = [module(0x1000), module(0x2000), module(0x3000)];
+
+        unsafe {
+            assert_eq!(registry.register_scoped(&modules[0]), Ok(()));
+            assert_eq!(
+                registry.register_scoped(&modules[0]),
+                Err(RegisterError::AlreadyRegistered)
+            );
+            assert_eq!(registry.register_scoped(&modules[1]), Ok(()));
+            assert_eq!(
+                registry.register_scoped(&modules[2]),
+                Err(RegisterError::Full)
+            );
+        }
+
+        assert_eq!(text_start(&registry, 0x1010), Some(0x1000));
+        assert_eq!(text_start(&registry, 0x2010), Some(0x2000));
+        assert_eq!(text_start(&registry, 0x3010), None);
+
+        assert!(registry.unregister(&modules[0]));
+        assert!(!registry.unregister(&modules[0]));
+        assert!(!registry.unregister(&modules[2]));
+        assert_eq!(text_start(&registry, 0x1010), None);
+
+        // The freed slot can be reused
+        unsafe {
+            assert_eq!(registry.register_scoped(&modules[2]), Ok(()));
+        }
+
+        assert_eq!(text_start(&registry, 0x3010), Some(0x3000));
+    }
+
+    #[test]
+    fn unregister_after_panicking_lookup() {
+        let registry = Registry::<2>::new();
+        let module = module(0x1000);
+
+        unsafe {
+            registry.register_scoped(&module).unwrap();
+        }
+
+        let result = panic::catch_unwind(|| {
+            registry.with_module(0x1010, |_| panic!("lookup failed"));
+        });
+
+        assert!(result.is_err());
+        assert!(registry.unregister(&module));
+    }
+
+    #[test]
+    fn unregister_during_lookups() {
+        let registry = Registry::<2>::new();
+        let modules = [module(0x1000), module(0x2000)];
+        let done = AtomicBool::new(false);
+
+        unsafe {
+            registry.register_scoped(&modules[0]).unwrap();
+        }
+
+        thread::scope(|scope| {
+            let lookups: Vec<_> = (0..2)
+                .map(|_| {
+                    scope.spawn(|| {
+                        while !done.load(Ordering::Relaxed) {
+                            assert_eq!(text_start(&registry, 0x1010), Some(0x1000));
+                            text_start(&registry, 0x2010);
+                        }
+                    })
+                })
+                .collect();
+
+            for _ in 0..1000 {
+                unsafe {
+                    registry.register_scoped(&modules[1]).unwrap();
+                }
+
+                assert!(registry.unregister(&modules[1]));
+            }
+
+            done.store(true, Ordering::Relaxed);
+
+            for lookup in lookups {
+                lookup.join().unwrap();
+            }
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use core::str;
+
+use zerocopy::{AsBytes, FromBytes, LayoutVerified};
+
+use crate::Module;
+
+/// One record of the `.sym` table written by rvind-analyze.
+#[derive(Debug, Clone, Copy, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct Symbol {
+    pub code_offset: u32,
+    pub name_offset: u32,
+}
+
+impl Symbol {
+    pub fn to_bytes(&self) -> &[u8] {
+        <_ as AsBytes>::as_bytes(self)
+    }
+}
+
+/// Maps code addresses to function names using the `.sym` and `.str` tables.
+#[derive(Debug, Clone, Copy)]
+pub struct SymbolTable<'a> {
+    symbols: &'a [Symbol],
+    strings: &'a [u8],
+}
+
+impl<'a> SymbolTable<'a> {
+    /// `symbols` must be sorted by `code_offset`.
+    pub fn new(symbols: &'a [Symbol], strings: &'a [u8]) -> Self {
+        Self { symbols, strings }
+    }
+
+    /// Returns `None` if `sym` is misaligned or not a whole number of records.
+    pub fn from_bytes(sym: &'a [u8], strings: &'a [u8]) -> Option<Self> {
+        let symbols = LayoutVerified::<_, [Symbol]>::new_slice(sym)?.into_slice();
+        Some(Self::new(symbols, strings))
+    }
+
+    fn name(&self, name_offset: u32) -> Option<&'a str> {
+        let bytes = self.strings.get(name_offset as usize..)?;
+        let len = bytes.iter().position(|&b| b == 0)?;
+        str::from_utf8(&bytes[..len]).ok()
+    }
+
+    /// Finds the function containing `pc` in the code of `module`, and the
+    /// offset of `pc` into it.
+    pub fn lookup(&self, module: &Module, pc: usize) -> Option<(&'a str, usize)> {
+        let offset: u32 = module.code_offset(pc)?.try_into().ok()?;
+        let index = self.symbols.partition_point(|s| s.code_offset <= offset);
+        let symbol = self.symbols.get(index.checked_sub(1)?)?;
+
+        Some((
+            self.name(symbol.name_offset)?,
+            (offset - symbol.code_offset) as usize,
+        ))
+    }
+}